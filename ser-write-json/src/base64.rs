@@ -1,46 +1,9 @@
 //! Base-64 codec.
+//!
+//! The encoder is re-exported from [`ser_write::base64`].
 use core::cell::Cell;
-use crate::SerWrite;
 
-static ALPHABET: &[u8;64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
-
-/// Encode an array of bytes as Base-64 ASCII armour codes into a [`SerWrite`] implementing object.
-///
-/// This function does not append Base-64 `'='` padding characters by itself
-/// and instead returns the number of padding characters required: 0-2.
-pub fn encode<W: SerWrite>(ser: &mut W, bytes: &[u8]) -> Result<u8, W::Error> {
-    let mut chunks = bytes.chunks_exact(3);
-    for slice in chunks.by_ref() {
-        let [a,b,c] = slice.try_into().unwrap();
-        let output = [
-            a >> 2,
-            ((a & 0x03) << 4) | ((b & 0xF0) >> 4),
-            ((b & 0x0F) << 2) | ((c & 0xC0) >> 6),
-            c & 0x3F
-        ].map(|n| ALPHABET[(n & 0x3F) as usize]);
-        ser.write(&output)?;
-    }
-    match chunks.remainder() {
-        [a, b] => {
-            let output = [
-                a >> 2,
-                ((a & 0x03) << 4) | ((b & 0xF0) >> 4),
-                ((b & 0x0F) << 2)
-            ].map(|n| ALPHABET[(n & 0x3F) as usize]);
-            ser.write(&output)?;
-            Ok(1)
-        }
-        [a] => {
-            let output = [
-                a >> 2,
-                ((a & 0x03) << 4),
-            ].map(|n| ALPHABET[(n & 0x3F) as usize]);
-            ser.write(&output)?;
-            Ok(2)
-        }
-        _ => Ok(0)
-    }
-}
+pub use ser_write::base64::{encode, Base64Writer};
 
 #[inline]
 fn get_code(c: u8) -> Option<u8> {
@@ -151,66 +114,7 @@ fn handle_tail<'a, I>(mut dcount: usize, mut packed: u32, mut dest: I) -> (usize
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ser_write::{SerError, SliceWriter};
-
-    #[test]
-    fn test_base64_encode() {
-        let mut buf = [0u8;6];
-        let writer = &mut SliceWriter::new(&mut buf);
-        encode(writer, &[]).unwrap();
-        assert_eq!(writer.as_ref(), b"");
-        encode(writer, &[0]).unwrap();
-        assert_eq!(writer.as_ref(), b"AA");
-        writer.clear();
-        encode(writer, &[1]).unwrap();
-        assert_eq!(writer.as_ref(), b"AQ");
-        writer.clear();
-        encode(writer, &[0,0]).unwrap();
-        assert_eq!(writer.as_ref(), b"AAA");
-        writer.clear();
-        encode(writer, &[0,0,0]).unwrap();
-        assert_eq!(writer.as_ref(), b"AAAA");
-        writer.clear();
-        encode(writer, &[0,0,0,0]).unwrap();
-        assert_eq!(writer.as_ref(), b"AAAAAA");
-        writer.clear();
-        encode(writer, &[1,2]).unwrap();
-        assert_eq!(writer.as_ref(), b"AQI");
-        writer.clear();
-        encode(writer, &[1,2,3]).unwrap();
-        assert_eq!(writer.as_ref(), b"AQID");
-        writer.clear();
-        encode(writer, &[1,2,3,4]).unwrap();
-        assert_eq!(writer.as_ref(), b"AQIDBA");
-        writer.clear();
-        encode(writer, &[0x80]).unwrap();
-        assert_eq!(writer.as_ref(), b"gA");
-        writer.clear();
-        encode(writer, &[0x80,0x81]).unwrap();
-        assert_eq!(writer.as_ref(), b"gIE");
-        writer.clear();
-        encode(writer, &[0x80,0x81,0x82]).unwrap();
-        assert_eq!(writer.as_ref(), b"gIGC");
-        writer.clear();
-        encode(writer, &[0xFF]).unwrap();
-        assert_eq!(writer.as_ref(), b"/w");
-        writer.clear();
-        encode(writer, &[0xFF,0xFF]).unwrap();
-        assert_eq!(writer.as_ref(), b"//8");
-        writer.clear();
-        encode(writer, &[0xFF,0xFF,0xFE]).unwrap();
-        assert_eq!(writer.as_ref(), b"///+");
-        writer.clear();
-        encode(writer, &[0xFF,0xFF,0xFF]).unwrap();
-        assert_eq!(writer.as_ref(), b"////");
-        assert_eq!(encode(writer, b"12345"), Err(SerError::BufferFull));
-        let mut buf = [0u8;1];
-        let writer = &mut SliceWriter::new(&mut buf);
-        assert_eq!(encode(writer, b"1"), Err(SerError::BufferFull));
-        let mut buf = [0u8;1];
-        let writer = &mut SliceWriter::new(&mut buf);
-        assert_eq!(encode(writer, b"12"), Err(SerError::BufferFull));
-    }
+    use crate::ser_write::{SerWrite, SliceWriter};
 
     fn test_decode(buf: &mut[u8], encoded: &[u8], expected: (usize, usize), decoded: &[u8]) {
         for i in 0..=4 {
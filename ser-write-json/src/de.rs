@@ -368,8 +368,8 @@ macro_rules! impl_checked_sub {
     )*};
 }
 
-impl_parse_tool!(u8, u16, u32, u64, i8, i16, i32, i64);
-impl_checked_sub!(i8, i16, i32, i64);
+impl_parse_tool!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+impl_checked_sub!(i8, i16, i32, i64, i128);
 
 #[cfg(feature = "de-any-f32")]
 type AnyFloat = f32;
@@ -379,6 +379,8 @@ type AnyFloat = f64;
 enum AnyNumber {
     PosInt(u64),
     NegInt(i64),
+    PosInt128(u128),
+    NegInt128(i128),
     Float(AnyFloat),
 }
 
@@ -583,6 +585,10 @@ impl<'de, P> Deserializer<'de, P> {
     /// call this method ONLY after ensuring the peek character is '0'..='9'|'-'
     #[inline]
     fn parse_float_or_int(&mut self, peek: u8) -> Result<AnyNumber> {
+        if let Some(token) = self.match_non_finite(0) {
+            self.eat_some(token.len());
+            return Ok(AnyNumber::Float(AnyFloat::from_str(token)?))
+        }
         let is_negative = peek == b'-';
         let mut is_float = false;
         let input = &self.input[self.index..];
@@ -604,10 +610,16 @@ impl<'de, P> Deserializer<'de, P> {
             AnyNumber::Float(AnyFloat::from_str(s)?)
         }
         else if is_negative {
-            AnyNumber::NegInt(i64::from_str(s)?)
+            match i64::from_str(s) {
+                Ok(n) => AnyNumber::NegInt(n),
+                Err(_) => AnyNumber::NegInt128(i128::from_str(s)?)
+            }
         }
         else {
-            AnyNumber::PosInt(u64::from_str(s)?)
+            match u64::from_str(s) {
+                Ok(n) => AnyNumber::PosInt(n),
+                Err(_) => AnyNumber::PosInt128(u128::from_str(s)?)
+            }
         };
         self.eat_some(input.len());
         Ok(num)
@@ -623,16 +635,42 @@ impl<'de, P> Deserializer<'de, P> {
         .unwrap_or(input)
     }
 
+    /// Return a non-finite float token: `NaN`, `Infinity` or `-Infinity`
+    /// found `offset` bytes after the cursor
+    #[inline]
+    fn match_non_finite(&self, offset: usize) -> Option<&'static str> {
+        let input = self.input.get(self.index + offset..).unwrap_or_default();
+        ["NaN", "Infinity", "-Infinity"].into_iter()
+        .find(|token| input.starts_with(token.as_bytes()))
+    }
+
     /// Consume whitespace and then parse a number as a float
+    ///
+    /// Non-finite values are parsed from the JSON5 tokens `NaN`, `Infinity` and `-Infinity`,
+    /// optionally quoted as JSON strings.
     #[inline]
     fn parse_float<E, F: FromStr<Err=E>>(&mut self) -> Result<Option<F>>
         where Error: From<E>
     {
-        if b'n' == self.eat_whitespace()? {
+        let peek = self.eat_whitespace()?;
+        if b'n' == peek {
             self.eat_some(1);
             self.parse_token_content(b"ull")?;
             return Ok(None)
         }
+        let quoted = b'"' == peek;
+        let skip = usize::from(quoted);
+        if let Some(token) = self.match_non_finite(skip) {
+            let v = F::from_str(token)?;
+            self.eat_some(skip + token.len());
+            if quoted {
+                self.parse_token_content(b"\"")?;
+            }
+            return Ok(Some(v))
+        }
+        else if quoted {
+            return Err(Error::InvalidNumber)
+        }
         let input = self.match_float();
         // SAFETY: We already checked that it only contains ASCII. This is only true if the
         // caller has guaranteed that `pattern` contains only ASCII characters.
@@ -742,7 +780,18 @@ impl<'de, P> Deserializer<'de, P> {
                                 // let code = u32::from_str_radix(s, 16)?;
                                 let code = self.input.get(index+1..index+5).ok_or(Error::UnexpectedEof)?
                                            .try_into().unwrap();
-                                let code = parse_uuuu(code).ok_or(Error::InvalidEscapeSequence)?;
+                                let mut code = parse_uuuu(code).ok_or(Error::InvalidEscapeSequence)?;
+                                if (0xD800..0xDC00).contains(&code) {
+                                    /* a high surrogate must be followed by an escaped low surrogate */
+                                    let low = match self.input.get(index+5..index+11) {
+                                        Some(&[RS, UU, a, b, c, d]) => parse_uuuu([a, b, c, d]),
+                                        _ => None
+                                    }
+                                    .filter(|low| (0xDC00..0xE000).contains(low))
+                                    .ok_or(Error::InvalidUnicodeCodePoint)?;
+                                    code = 0x10000 + ((code - 0xD800) << 10) + (low - 0xDC00);
+                                    index += 6;
+                                }
                                 let ch = char::from_u32(code).ok_or(Error::InvalidUnicodeCodePoint)?;
                                 dest += ch.encode_utf8(&mut self.input[dest..]).len();
                                 index += 5;
@@ -905,9 +954,11 @@ impl<'de, P> de::Deserializer<'de> for &mut Deserializer<'de, P>
             b'n' => self.deserialize_unit(visitor),
             b't'|b'f' => self.deserialize_bool(visitor),
             b'"' => self.deserialize_str(visitor),
-            c@(b'0'..=b'9'|b'-') => match self.parse_float_or_int(c)? {
+            c@(b'0'..=b'9'|b'-'|b'N'|b'I') => match self.parse_float_or_int(c)? {
                 AnyNumber::PosInt(n) => visitor.visit_u64(n),
                 AnyNumber::NegInt(n) => visitor.visit_i64(n),
+                AnyNumber::PosInt128(n) => visitor.visit_u128(n),
+                AnyNumber::NegInt128(n) => visitor.visit_i128(n),
                 #[cfg(feature = "de-any-f32")]
                 AnyNumber::Float(f) => visitor.visit_f32(f),
                 #[cfg(not(feature = "de-any-f32"))]
@@ -986,6 +1037,18 @@ impl<'de, P> de::Deserializer<'de> for &mut Deserializer<'de, P>
         visitor.visit_u64(self.parse_unsigned()?)
     }
 
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_i128(self.parse_signed()?)
+    }
+
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_u128(self.parse_unsigned()?)
+    }
+
     fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value>
         where V: Visitor<'de>
     {
@@ -1210,8 +1273,14 @@ impl<'de, P> de::Deserializer<'de> for &mut Deserializer<'de, P>
                 self.eat_str_content()?;
                 visitor.visit_unit()
             }
-            b'0'..=b'9'|b'-' => {
-                let len = self.match_float().len();
+            b'0'..=b'9'|b'-'|b'N'|b'I' => {
+                let len = match self.match_non_finite(0) {
+                    Some(token) => token.len(),
+                    None => self.match_float().len()
+                };
+                if len == 0 {
+                    return Err(Error::UnexpectedChar)
+                }
                 self.eat_some(len);
                 visitor.visit_unit()
             }
@@ -1418,6 +1487,18 @@ impl<'de, 'a, P> de::Deserializer<'de> for MapKey<'a, 'de, P>
         visitor.visit_u64(self.parse_unsigned_numkey()?)
     }
 
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_i128(self.parse_signed_numkey()?)
+    }
+
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value>
+        where V: Visitor<'de>
+    {
+        visitor.visit_u128(self.parse_unsigned_numkey()?)
+    }
+
     fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
         where V: Visitor<'de>
     {
@@ -1444,7 +1525,7 @@ impl<'de, 'a, P> de::Deserializer<'de> for MapKey<'a, 'de, P>
     }
 
     forward_to_deserialize_any! {
-        i128 u128 f32 f64 string
+        f32 f64 string
         bytes byte_buf option unit unit_struct newtype_struct seq tuple
         tuple_struct map struct identifier ignored_any
     }
@@ -2036,6 +2117,21 @@ mod tests {
         assert_eq!(from_str::<f64>(","), Err(Error::InvalidNumber));
         assert_eq!(from_str::<f32>("-"), Err(Error::InvalidNumber));
         assert_eq!(from_str::<f64>("-"), Err(Error::InvalidNumber));
+        for input in ["NaN", r#""NaN""#] {
+            let (f, len): (f32, _) = from_str(input).unwrap();
+            assert_eq!(len, input.len());
+            assert!(f.is_nan());
+            let (f, _): (f64, _) = from_str(input).unwrap();
+            assert!(f.is_nan());
+        }
+        assert_eq!(from_str("Infinity"), Ok((f32::INFINITY, 8)));
+        assert_eq!(from_str(r#""Infinity""#), Ok((f64::INFINITY, 10)));
+        assert_eq!(from_str("-Infinity"), Ok((f64::NEG_INFINITY, 9)));
+        assert_eq!(from_str(r#" "-Infinity" "#), Ok((f32::NEG_INFINITY, 13)));
+        assert_eq!(from_str::<f32>(r#""Infinity"#), Err(Error::UnexpectedEof));
+        assert_eq!(from_str::<f32>(r#""Infinity'"#), Err(Error::ExpectedToken));
+        assert_eq!(from_str::<f64>(r#""1.0""#), Err(Error::InvalidNumber));
+        assert_eq!(from_str::<f64>("Inf"), Err(Error::InvalidNumber));
     }
 
     #[test]
@@ -2069,11 +2165,16 @@ mod tests {
                 assert_eq!(from_str::<$ty>("1234567890123456789012345678901234567890"), Err(Error::InvalidNumber));
             )*};
         }
-        test_de_signed!(i8,i16,i32,i64);
-        test_de_unsigned!(u8,u16,u32,u64);
-        test_de_int!(i8,i16,i32,i64,u8,u16,u32,u64);
+        test_de_signed!(i8,i16,i32,i64,i128);
+        test_de_unsigned!(u8,u16,u32,u64,u128);
+        test_de_int!(i8,i16,i32,i64,i128,u8,u16,u32,u64,u128);
         assert_eq!(from_str::<u8>("256"), Err(Error::InvalidNumber));
         assert_eq!(from_str::<i8>("-129"), Err(Error::InvalidNumber));
+        assert_eq!(from_str::<u128>("340282366920938463463374607431768211455"), Ok((u128::MAX, 39)));
+        assert_eq!(from_str::<u128>("340282366920938463463374607431768211456"), Err(Error::InvalidNumber));
+        assert_eq!(from_str::<i128>("-170141183460469231731687303715884105728"), Ok((i128::MIN, 40)));
+        assert_eq!(from_str::<i128>("-170141183460469231731687303715884105729"), Err(Error::InvalidNumber));
+        assert_eq!(from_str::<i128>("170141183460469231731687303715884105728"), Err(Error::InvalidNumber));
     }
 
     #[test]
@@ -2117,6 +2218,7 @@ mod tests {
         assert_eq!(from_bufstr(buf, r#" " " "#), Ok((" ", 5)));
         assert_eq!(from_bufstr(buf, r#" "👏" "#), Ok(("👏", 8)));
 
+        assert_eq!(from_bufstr(buf, r#" "\uD83D\uDC4F" "#), Ok(("👏", 16)));
         assert_eq!(from_bufstr(buf, r#" "hel\tlo" "#), Ok(("hel\tlo", 11)));
         assert_eq!(from_bufstr(buf, r#" "hello \\" "#), Ok(("hello \\", 12)));
 
@@ -2163,6 +2265,9 @@ mod tests {
         assert_eq!(from_bufstr::<&str>(buf, r#" "\u000" "#), Err(Error::InvalidEscapeSequence));
         assert_eq!(from_bufstr::<&str>(buf, r#" "\uD800" "#), Err(Error::InvalidUnicodeCodePoint));
         assert_eq!(from_bufstr::<&str>(buf, r#" "\uDFFF" "#), Err(Error::InvalidUnicodeCodePoint));
+        assert_eq!(from_bufstr::<&str>(buf, r#" "\uD83C\u0041" "#), Err(Error::InvalidUnicodeCodePoint));
+        assert_eq!(from_bufstr::<&str>(buf, r#" "\uD83C\uD83C" "#), Err(Error::InvalidUnicodeCodePoint));
+        assert_eq!(from_bufstr::<&str>(buf, r#" "\uD83C\n" "#), Err(Error::InvalidUnicodeCodePoint));
         buf[0..4].copy_from_slice(b"\"\xff\xfe\"");
         assert_eq!(from_mut_slice::<&str>(&mut buf[0..4]), Err(Error::InvalidUnicodeCodePoint));
     }
@@ -2228,6 +2333,25 @@ mod tests {
         assert_eq!(from_str::<Test>(r#"{,"foo":0}"#), Err(Error::LeadingObjectComma));
     }
 
+    #[test]
+    fn test_de_ignored_non_finite() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Test {
+            foo: i8
+        }
+        for (input, len) in [
+            (r#"{"nan":NaN,"foo":1}"#, 19),
+            (r#"{"inf":Infinity,"foo":1}"#, 24),
+            (r#"{"foo":1, "neg": -Infinity }"#, 28),
+            (r#"{"skip":[NaN,-Infinity,Infinity,-1.5e3],"foo":1}"#, 48),
+        ] {
+            assert_eq!(from_str(input), Ok((Test { foo: 1 }, len)));
+        }
+        assert_eq!(from_str::<Test>(r#"{"nan":Nan,"foo":1}"#), Err(Error::UnexpectedChar));
+        assert_eq!(from_str::<Test>(r#"{"inf":Inf,"foo":1}"#), Err(Error::UnexpectedChar));
+        assert_eq!(from_str::<Test>(r#"{"inf":-Inf,"foo":1}"#), Err(Error::ExpectedObjectCommaOrEnd));
+    }
+
     #[test]
     fn test_de_struct_bool() {
         #[derive(Debug, Deserialize, PartialEq)]
@@ -2646,7 +2770,7 @@ mod tests {
     #[cfg(any(feature = "std", feature = "alloc"))]
     #[test]
     fn test_de_map() {
-        let buf = &mut [0u8;160];
+        let buf = &mut [0u8;224];
         macro_rules! test_de_map_int {
             ($($ty:ty),*) => {$(
                 let mut amap = BTreeMap::<$ty,&str>::new();
@@ -2696,7 +2820,7 @@ mod tests {
                     Err(Error::InvalidType));
             )*};
         }
-        test_de_map_int!(i8, u8, i16, u16, i32, u32, i64, u64);
+        test_de_map_int!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128);
         let mut amap = BTreeMap::<&str,Option<bool>>::new();
         amap.insert("", None);
         amap.insert("  ", Some(false));
@@ -2906,6 +3030,43 @@ mod tests {
         assert_eq!(
             from_bufstr(&mut buf, input),
             Ok((Thing::LongInt(i64::MIN), input.len())));
+        #[derive(Debug, PartialEq)]
+        enum Wide {
+            Uint(u128),
+            Int(i128)
+        }
+        impl<'de> Deserialize<'de> for Wide {
+            fn deserialize<D: de::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+                struct WideVisitor;
+                impl<'de> Visitor<'de> for WideVisitor {
+                    type Value = Wide;
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str("an integer")
+                    }
+                    fn visit_u64<E: de::Error>(self, v: u64) -> core::result::Result<Wide, E> {
+                        Ok(Wide::Uint(v.into()))
+                    }
+                    fn visit_u128<E: de::Error>(self, v: u128) -> core::result::Result<Wide, E> {
+                        Ok(Wide::Uint(v))
+                    }
+                    fn visit_i128<E: de::Error>(self, v: i128) -> core::result::Result<Wide, E> {
+                        Ok(Wide::Int(v))
+                    }
+                }
+                deserializer.deserialize_any(WideVisitor)
+            }
+        }
+        let mut wide = [0u8;40];
+        let input = "18446744073709551615";
+        assert_eq!(from_bufstr(&mut wide, input), Ok((Wide::Uint(u64::MAX.into()), input.len())));
+        let input = "18446744073709551616";
+        assert_eq!(from_bufstr(&mut wide, input), Ok((Wide::Uint(u128::from(u64::MAX) + 1), input.len())));
+        let input = "340282366920938463463374607431768211455";
+        assert_eq!(from_bufstr(&mut wide, input), Ok((Wide::Uint(u128::MAX), input.len())));
+        let input = "-170141183460469231731687303715884105728";
+        assert_eq!(from_bufstr(&mut wide, input), Ok((Wide::Int(i128::MIN), input.len())));
+        let input = "340282366920938463463374607431768211456";
+        assert_eq!(from_bufstr::<Wide>(&mut wide, input), Err(Error::InvalidNumber));
         let input = "0.0";
         assert_eq!(
             from_bufstr(&mut buf, input),
@@ -2919,6 +3080,17 @@ mod tests {
         assert_eq!(
             from_bufstr(&mut buf, input),
             Ok((Thing::Float(3.40282347E+38), input.len())));
+        let input = "-Infinity";
+        assert_eq!(
+            from_bufstr(&mut buf, input),
+            Ok((Thing::Float(f64::NEG_INFINITY), input.len())));
+        let input = "Infinity";
+        assert_eq!(
+            from_bufstr(&mut buf, input),
+            Ok((Thing::Float(f64::INFINITY), input.len())));
+        let input = "NaN";
+        assert!(matches!(from_bufstr(&mut buf, input),
+            Ok((Thing::Float(f), 3)) if f.is_nan()));
         let input = "1.7976931348623157e308";
         #[cfg(feature = "de-any-f32")]
         assert_eq!(
@@ -1,4 +1,4 @@
-//! A JSON (compact) serde serializer for [`ser-write`](`ser_write`) and a JSON deserializer for convenience.
+//! A JSON (compact or pretty) serde serializer for [`ser-write`](`ser_write`) and a JSON deserializer for convenience.
 /*!
 
 [`Serializer`] types:
@@ -11,8 +11,8 @@
 | `NewType(T)`      | `T` -> `JSON`
 | `None`            | `null`
 | `Some(T)`         | `T` -> `JSON`
-| `u8`-`u64`        | `number`
-| `i8`-`i64`        | `number`
+| `u8`-`u128`       | `number`
+| `i8`-`i128`       | `number`
 | `f23`,`f64`       | `number`
 | `str`             | `string`
 | `bytes`           | (configurable)
@@ -27,6 +27,7 @@
 
 * [`Serializer`] supports serializing map keys as JSON strings from integers, chars, bools
   and C-like enums.
+* [`Serializer`] serializes non-finite floats according to the [`NonFinite`] policy.
 
 [`Deserializer`] types:
 
@@ -34,7 +35,8 @@
 |-------------------|----------------------------------------
 | `null`            | `unit`,`none`,`NaN`
 | `boolean`         | `bool`
-| `number`          | `f64`,`f32`,`u8`-`u64`,`i8`-`i64`
+| `number`          | `f64`,`f32`,`u8`-`u128`,`i8`-`i128`
+| `NaN`,`Infinity`  | `f64`,`f32` (also from JSON strings)
 | `string`          | `str`,`bytes` (configurable),`enum variant`
 | `array`           | `array`,`tuple`,`tuple struct`,`typle variant`,`seq-like`,`struct`
 | `object`          | `enum variant`,`struct variant`,`map-like`,`struct`
@@ -48,6 +50,7 @@
 [`Serializer`]: ser::Serializer
 [`Deserializer`]: de::Deserializer
 [`deserialize_any`]: serde::de::Deserializer::deserialize_any
+[`NonFinite`]: ser::NonFinite
 */
 #![no_std]
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -59,6 +62,7 @@ extern crate std;
 extern crate alloc;
 
 pub mod base64;
+pub use ser_write::hex;
 pub mod ser;
 pub mod de;
 
@@ -70,14 +74,34 @@ pub use ser::{
     to_string,
     to_string_hex_bytes,
     to_string_base64_bytes,
-    to_string_pass_bytes
+    to_string_pass_bytes,
+    to_string_pretty
 };
+#[cfg(feature = "heapless")]
+pub use ser::to_heapless_string;
+#[cfg(feature = "arrayvec")]
+pub use ser::to_array_string;
 pub use ser::{
     to_writer_with_encoder,
     to_writer,
     to_writer_hex_bytes,
     to_writer_base64_bytes,
-    to_writer_pass_bytes
+    to_writer_pass_bytes,
+    to_writer_pretty_with_encoder,
+    to_writer_pretty,
+    to_writer_atomic_with_encoder,
+    to_writer_atomic,
+    serialized_size_with_encoder,
+    serialized_size,
+    to_window_with_encoder,
+    to_window,
+    to_dyn_writer_with_encoder,
+    to_dyn_writer
+};
+#[cfg(feature = "async")]
+pub use ser::{
+    to_writer_async_with_encoder,
+    to_writer_async
 };
 pub use de::{
     from_mut_slice_with_decoder,
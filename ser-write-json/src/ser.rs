@@ -11,6 +11,10 @@ use alloc::{vec::Vec, string::{String, ToString}};
 
 use serde::{ser, Serialize};
 use crate::SerWrite;
+use ser_write::hex::encode_byte;
+use ser_write::{SerCheckpoint, ArrayWriter, CountingWriter, DynSerWrite, Window, WindowWriter};
+#[cfg(feature = "async")]
+use ser_write::{AsyncSerWrite, AsyncSerError, SerError};
 
 /// JSON serializer serializing bytes to an array of numbers
 pub type SerializerByteArray<W> = Serializer<W, ArrayByteEncoder>;
@@ -26,9 +30,21 @@ pub type SerializerBytePass<W> = Serializer<W, PassThroughByteEncoder>;
 /// `W` - should implement [`SerWrite`] and `B` - [`ByteEncoder`].
 ///
 /// `ByteEncoder` determines [`ser::Serializer::serialize_bytes`] implementation.
+///
+/// The output is compact unless the serializer is created with [`Serializer::new_pretty`].
+///
+/// Non-finite floats are serialized as `null` unless configured otherwise with
+/// [`Serializer::with_non_finite`]. Finite floats are serialized in the shortest
+/// form unless configured otherwise with [`Serializer::with_float_format`].
+///
+/// Strings are escaped minimally unless configured otherwise with [`Serializer::with_escape`].
 pub struct Serializer<W, B> {
     output: W,
-    format: PhantomData<B>
+    format: PhantomData<B>,
+    pretty: Option<Pretty>,
+    non_finite: NonFinite,
+    float_format: FloatFormat,
+    escape: EscapeConfig
 }
 
 /// Serialization error
@@ -45,6 +61,8 @@ pub enum Error<E> {
     Utf8Encode,
     /// Error formatting a collected string
     FormatError,
+    /// A non-finite float with [`NonFinite::Error`] policy
+    NonFiniteFloat,
     #[cfg(any(feature = "std", feature = "alloc"))]
     #[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
     /// An error passed down from a [`serde::ser::Serialize`] implementation
@@ -66,6 +84,7 @@ impl<E: fmt::Display> fmt::Display for Error<E> {
             #[cfg(any(feature = "std", feature = "alloc"))]
             Error::Utf8Encode => f.write_str("error encoding JSON as UTF-8 string"),
             Error::FormatError => f.write_str("error while collecting a string"),
+            Error::NonFiniteFloat => f.write_str("non-finite float can't be serialized to JSON"),
             #[cfg(any(feature = "std", feature = "alloc"))]
             Error::SerializeError(s) => write!(f, "{} while serializing JSON", s),
             #[cfg(not(any(feature = "std", feature = "alloc")))]
@@ -157,6 +176,138 @@ impl ByteEncoder for PassThroughByteEncoder {
     }
 }
 
+/// Determine how non-finite floats: NaN and ±Infinity are serialized
+///
+/// The [`Deserializer`](crate::de::Deserializer) accepts all of the produced
+/// representations as floats.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum NonFinite {
+    /// Serialize as `null`, deserialized back as NaN
+    #[default]
+    Null,
+    /// Fail with [`Error::NonFiniteFloat`]
+    Error,
+    /// Serialize as strings: `"NaN"`, `"Infinity"` or `"-Infinity"`
+    String,
+    /// Serialize as the bare JSON5 tokens: `NaN`, `Infinity` or `-Infinity`
+    ///
+    /// **NOTE**: the output will not be a valid JSON!
+    Json5
+}
+
+/// Determine how finite floats are serialized
+///
+/// Digits are rounded correctly from the exact binary value, with ties to even.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FloatFormat {
+    /// The shortest representation that parses back to the same value
+    #[default]
+    Shortest,
+    /// A fixed number of digits after the decimal point, e.g. `Fixed(2)`
+    /// serializes `21.299999` as `21.30`
+    Fixed(u8),
+    /// At most the given number of significant digits, in the range `1..=17`,
+    /// without trailing zeros, e.g. `Significant(3)` serializes `21.299999` as `21.3`
+    Significant(u8)
+}
+
+/// String escaping options for [`Serializer::with_escape`] and [`StringCollector::with_escape`].
+///
+/// Control characters, `"` and `\` are always escaped. By default nothing else is.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EscapeConfig {
+    ascii: bool,
+    html: bool,
+    slash: bool
+}
+
+impl EscapeConfig {
+    /// Create the default configuration
+    pub const fn new() -> Self {
+        EscapeConfig { ascii: false, html: false, slash: false }
+    }
+    /// Escape all non-ASCII characters as `\uXXXX`, using surrogate pairs for characters
+    /// outside of the Basic Multilingual Plane.
+    pub const fn ascii(self, ascii: bool) -> Self {
+        EscapeConfig { ascii, ..self }
+    }
+    /// Escape `<`, `>`, `&`, `'`, U+2028 and U+2029 as `\uXXXX`, so the output
+    /// can be embedded in HTML and JavaScript.
+    pub const fn html(self, html: bool) -> Self {
+        EscapeConfig { html, ..self }
+    }
+    /// Escape `/` as `\/`.
+    pub const fn slash(self, slash: bool) -> Self {
+        EscapeConfig { slash, ..self }
+    }
+}
+
+/// Indentation of the pretty printed JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Indent {
+    /// Indent with the given number of spaces per nesting level
+    Spaces(u8),
+    /// Indent with the given number of tabs per nesting level
+    Tabs(u8)
+}
+
+/// Line endings of the pretty printed JSON
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Newline {
+    /// `"\n"`
+    #[default]
+    Lf,
+    /// `"\r\n"`
+    CrLf
+}
+
+/// Pretty printing options for [`Serializer::new_pretty`].
+///
+/// By default, nested values are indented with 2 spaces and lines end with `"\n"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PrettyConfig {
+    indent: Indent,
+    newline: Newline,
+    inline_len: usize
+}
+
+impl Default for PrettyConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrettyConfig {
+    /// Create the default configuration
+    pub const fn new() -> Self {
+        PrettyConfig { indent: Indent::Spaces(2), newline: Newline::Lf, inline_len: 0 }
+    }
+    /// Set the indentation
+    pub const fn indent(self, indent: Indent) -> Self {
+        PrettyConfig { indent, ..self }
+    }
+    /// Set the line endings
+    pub const fn newline(self, newline: Newline) -> Self {
+        PrettyConfig { newline, ..self }
+    }
+    /// Keep arrays and tuples of up to `max_len` elements on a single line, together
+    /// with any values nested in them.
+    ///
+    /// Only sequences with a length known in advance are considered. By default `0`.
+    pub const fn inline_arrays(self, max_len: usize) -> Self {
+        PrettyConfig { inline_len: max_len, ..self }
+    }
+}
+
+/// The state of the pretty printer
+#[derive(Debug, Clone, Copy)]
+struct Pretty {
+    config: PrettyConfig,
+    depth: usize,
+    /// The nesting level of containers written on a single line
+    inline: usize
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 #[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
 pub fn to_string<T>(value: &T) -> Result<String, ser_write::SerError>
@@ -200,6 +351,42 @@ pub fn to_string_pass_bytes<T>(value: &T) -> Result<String, ser_write::SerError>
     String::from_utf8(vec).map_err(|_| Error::Utf8Encode)
 }
 
+/// Serialize `value` as pretty printed JSON to a new string according to the provided `config`.
+///
+/// Serialize bytes as arrays of numbers.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
+pub fn to_string_pretty<T>(config: PrettyConfig, value: &T) -> Result<String, ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    let mut vec = Vec::new();
+    to_writer_pretty(&mut vec, config, value)?;
+    // SAFETY: SerializerByteArray produce a valid UTF-8 output
+    Ok(unsafe { String::from_utf8_unchecked(vec) })
+}
+
+/// Serialize `value` as JSON to a new [`heapless::String`] of capacity `N`.
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub fn to_heapless_string<const N: usize, T>(value: &T) -> Result<heapless::String<N>, ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    let mut string = heapless::String::new();
+    to_writer(&mut string, value)?;
+    Ok(string)
+}
+
+/// Serialize `value` as JSON to a new [`arrayvec::ArrayString`] of capacity `N`.
+#[cfg(feature = "arrayvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+pub fn to_array_string<const N: usize, T>(value: &T) -> Result<arrayvec::ArrayString<N>, ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    let mut string = arrayvec::ArrayString::new();
+    to_writer(&mut string, value)?;
+    Ok(string)
+}
+
 /// Serialize `value` as JSON to a [`SerWrite`] implementation using a provided [`ByteEncoder`].
 pub fn to_writer_with_encoder<B, W, T>(writer: W, value: &T) -> Result<(), W::Error>
     where B: ByteEncoder,
@@ -258,12 +445,217 @@ pub fn to_writer_pass_bytes<W, T>(writer: W, value: &T) -> Result<(), W::Error>
     to_writer_with_encoder::<PassThroughByteEncoder, _, _>(writer, value)
 }
 
+/// Serialize `value` as pretty printed JSON to a [`SerWrite`] implementation using a provided
+/// [`ByteEncoder`] and [`PrettyConfig`].
+pub fn to_writer_pretty_with_encoder<B, W, T>(writer: W, config: PrettyConfig, value: &T) -> Result<(), W::Error>
+    where B: ByteEncoder,
+          W: SerWrite,
+          <W as SerWrite>::Error: fmt::Display + fmt::Debug,
+          T: Serialize + ?Sized
+{
+    let mut serializer = Serializer::<_, B>::new_pretty(writer, config);
+    value.serialize(&mut serializer)
+}
+
+/// Serialize `value` as pretty printed JSON to a [`SerWrite`] implementation.
+///
+/// Serialize bytes as arrays of numbers.
+pub fn to_writer_pretty<W, T>(writer: W, config: PrettyConfig, value: &T) -> Result<(), W::Error>
+    where W: SerWrite,
+          <W as SerWrite>::Error: fmt::Display + fmt::Debug,
+          T: Serialize + ?Sized
+{
+    to_writer_pretty_with_encoder::<ArrayByteEncoder, _, _>(writer, config, value)
+}
+
+/// Serialize `value` as JSON to a [`DynSerWrite`] trait object using a provided [`ByteEncoder`].
+///
+/// The serializer is compiled once for all writers instead of once per writer type.
+pub fn to_dyn_writer_with_encoder<B, T>(writer: &mut dyn DynSerWrite, value: &T) -> Result<(), ser_write::SerError>
+    where B: ByteEncoder,
+          T: Serialize + ?Sized
+{
+    to_writer_with_encoder::<B, _, _>(writer, value)
+}
+
+/// Serialize `value` as JSON to a [`DynSerWrite`] trait object.
+///
+/// Serialize bytes as arrays of numbers.
+///
+/// The serializer is compiled once for all writers instead of once per writer type.
+/// Writers with error types other than [`ser_write::SerError`] can be
+/// wrapped in [`ser_write::DynWriter`].
+pub fn to_dyn_writer<T>(writer: &mut dyn DynSerWrite, value: &T) -> Result<(), ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    to_dyn_writer_with_encoder::<ArrayByteEncoder, _>(writer, value)
+}
+
+/// Return the exact length of `value` serialized as JSON using a provided [`ByteEncoder`].
+///
+/// The value is serialized without storing the output anywhere.
+pub fn serialized_size_with_encoder<B, T>(value: &T) -> Result<usize, ser_write::SerError>
+    where B: ByteEncoder,
+          T: Serialize + ?Sized
+{
+    let mut counter = CountingWriter::new();
+    to_writer_with_encoder::<B, _, _>(&mut counter, value)?;
+    Ok(counter.count())
+}
+
+/// Return the exact length of `value` serialized as JSON with bytes serialized as arrays of numbers.
+///
+/// The value is serialized without storing the output anywhere.
+pub fn serialized_size<T>(value: &T) -> Result<usize, ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    serialized_size_with_encoder::<ArrayByteEncoder, _>(value)
+}
+
+/// Serialize `value` as JSON using a provided [`ByteEncoder`] and capture only the bytes
+/// of the output in the range `[offset, offset + buf.len())` to `buf`.
+///
+/// See [`WindowWriter`] for details.
+pub fn to_window_with_encoder<'a, B, T>(
+        buf: &'a mut [u8],
+        offset: usize,
+        value: &T
+    ) -> Result<Window<'a>, ser_write::SerError>
+    where B: ByteEncoder,
+          T: Serialize + ?Sized
+{
+    let mut writer = WindowWriter::new(buf, offset);
+    to_writer_with_encoder::<B, _, _>(&mut writer, value)?;
+    Ok(writer.finish())
+}
+
+/// Serialize `value` as JSON and capture only the bytes of the output in the range
+/// `[offset, offset + buf.len())` to `buf`.
+///
+/// Serialize bytes as arrays of numbers.
+///
+/// The returned [`Window`] contains the total length of the output and indicates
+/// whether the window has been filled completely. Repeat with the `offset` advanced
+/// by the length of the captured data until the window is not full to transmit
+/// the whole message in pages.
+///
+/// # Panics
+/// Panics if `buf` is empty.
+pub fn to_window<'a, T>(buf: &'a mut [u8], offset: usize, value: &T) -> Result<Window<'a>, ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    to_window_with_encoder::<ArrayByteEncoder, _>(buf, offset, value)
+}
+
+/// Serialize `value` as JSON to a [`SerCheckpoint`] implementation using a provided [`ByteEncoder`].
+///
+/// If the serialization fails, the `writer` is left exactly as it was before the call.
+pub fn to_writer_atomic_with_encoder<B, W, T>(writer: &mut W, value: &T) -> Result<(), W::Error>
+    where B: ByteEncoder,
+          W: SerCheckpoint + ?Sized,
+          <W as SerWrite>::Error: fmt::Display + fmt::Debug,
+          T: Serialize + ?Sized
+{
+    ser_write::write_atomic(writer, |tx| to_writer_with_encoder::<B, _, _>(tx, value))
+}
+
+/// Serialize `value` as JSON to a [`SerCheckpoint`] implementation.
+///
+/// Serialize bytes as arrays of numbers.
+///
+/// If the serialization fails, the `writer` is left exactly as it was before the call.
+pub fn to_writer_atomic<W, T>(writer: &mut W, value: &T) -> Result<(), W::Error>
+    where W: SerCheckpoint + ?Sized,
+          <W as SerWrite>::Error: fmt::Display + fmt::Debug,
+          T: Serialize + ?Sized
+{
+    to_writer_atomic_with_encoder::<ArrayByteEncoder, _, _>(writer, value)
+}
+
+/// Serialize `value` as JSON to an [`AsyncSerWrite`] implementation using a provided [`ByteEncoder`].
+///
+/// The JSON is streamed to the `writer` in chunks of up to `buf.len()` bytes. The `value`
+/// is serialized once for each chunk, see [`write_async_with`] for details. To serialize
+/// many values once each, pass [`to_writer`] to [`AsyncBufWriter::write_with`] instead.
+///
+/// The returned future is pending whenever the `writer` applies backpressure.
+///
+/// # Panics
+/// Panics if `buf` is empty.
+///
+/// [`AsyncSerWrite`]: ser_write::AsyncSerWrite
+/// [`AsyncBufWriter::write_with`]: ser_write::AsyncBufWriter::write_with
+/// [`write_async_with`]: ser_write::write_async_with
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub async fn to_writer_async_with_encoder<B, W, T>(
+        writer: W,
+        buf: &mut [u8],
+        value: &T
+    ) -> core::result::Result<(), AsyncSerError<Error<SerError>, W::Error>>
+    where B: ByteEncoder,
+          W: AsyncSerWrite,
+          T: Serialize + ?Sized
+{
+    ser_write::write_async_with(writer, buf, |stage| to_writer_with_encoder::<B, _, _>(stage, value)).await
+}
+
+/// Serialize `value` as JSON to an [`AsyncSerWrite`] implementation.
+///
+/// Serialize bytes as arrays of numbers.
+///
+/// See [`to_writer_async_with_encoder`] for details.
+///
+/// [`AsyncSerWrite`]: ser_write::AsyncSerWrite
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub async fn to_writer_async<W, T>(
+        writer: W,
+        buf: &mut [u8],
+        value: &T
+    ) -> core::result::Result<(), AsyncSerError<Error<SerError>, W::Error>>
+    where W: AsyncSerWrite,
+          T: Serialize + ?Sized
+{
+    to_writer_async_with_encoder::<ArrayByteEncoder, _, _>(writer, buf, value).await
+}
+
 impl<W, B> Serializer<W, B> {
     /// Create a new `Serializer` with the given `output` object that should
     /// implement [`SerWrite`].
     #[inline(always)]
     pub fn new(output: W) -> Self {
-        Serializer { output, format: PhantomData }
+        Serializer {
+            output,
+            format: PhantomData,
+            pretty: None,
+            non_finite: NonFinite::Null,
+            float_format: FloatFormat::Shortest,
+            escape: EscapeConfig::new()
+        }
+    }
+    /// Create a new `Serializer` pretty printing JSON to the given `output` object
+    /// according to the provided `config`.
+    #[inline]
+    pub fn new_pretty(output: W, config: PrettyConfig) -> Self {
+        let pretty = Pretty { config, depth: 0, inline: 0 };
+        Serializer { pretty: Some(pretty), ..Serializer::new(output) }
+    }
+    /// Set the policy of serializing non-finite floats.
+    #[inline]
+    pub fn with_non_finite(self, non_finite: NonFinite) -> Self {
+        Serializer { non_finite, ..self }
+    }
+    /// Set the format of serialized finite floats.
+    #[inline]
+    pub fn with_float_format(self, float_format: FloatFormat) -> Self {
+        Serializer { float_format, ..self }
+    }
+    /// Set the string escaping options applied to object keys, string values
+    /// and collected strings.
+    #[inline]
+    pub fn with_escape(self, escape: EscapeConfig) -> Self {
+        Serializer { escape, ..self }
     }
     /// Destruct self returning the `output` object.
     #[inline(always)]
@@ -285,27 +677,122 @@ impl<W, B> Serializer<W, B> {
 impl<W: SerWrite, B> Serializer<W, B> {
     /// Serialize given slice of bytes as ASCII HEX nibbles
     pub fn serialize_bytes_as_hex_str(&mut self, v: &[u8]) -> Result<(), W::Error> {
-        let writer = self.writer();
-        for &byte in v.iter() {
-            writer.write(&hex(byte))?;
+        Ok(crate::hex::encode(self.writer(), v)?)
+    }
+    /// Serialize a NaN or an infinite float according to the [`NonFinite`] policy.
+    fn serialize_non_finite(&mut self, v: f64) -> Result<(), W::Error> {
+        let token: &[u8] = if v.is_nan() {
+            b"\"NaN\""
         }
-        Ok(())
+        else if v.is_sign_negative() {
+            b"\"-Infinity\""
+        }
+        else {
+            b"\"Infinity\""
+        };
+        match self.non_finite {
+            NonFinite::Null => Ok(self.output.write(b"null")?),
+            NonFinite::Error => Err(Error::NonFiniteFloat),
+            NonFinite::String => Ok(self.output.write(token)?),
+            NonFinite::Json5 => Ok(self.output.write(&token[1..token.len() - 1])?)
+        }
+    }
+    /// Serialize a finite float with a fixed number of `decimals`.
+    ///
+    /// The digits are correctly rounded by [`core::fmt`] from the exact value of `v`.
+    fn serialize_float_fixed(&mut self, v: f64, decimals: u8) -> Result<(), W::Error> {
+        let mut fw = FmtWriter { output: &mut self.output, error: None };
+        match fmt::write(&mut fw, format_args!("{:.*}", usize::from(decimals), v)) {
+            Ok(()) => Ok(()),
+            Err(_) => Err(fw.error.map_or(Error::FormatError, Error::Writer))
+        }
+    }
+    /// Serialize a finite float with at most the given number of significant `digits`.
+    ///
+    /// The digits are correctly rounded by [`core::fmt`] from the exact value of `v`.
+    fn serialize_float_significant(&mut self, v: f64, digits: u8) -> Result<(), W::Error> {
+        let precision = usize::from(digits.clamp(1, 17)) - 1;
+        // "-1.2345678901234567e-308"
+        let mut buf = ArrayWriter::<24>::new();
+        fmt::write(&mut buf, format_args!("{:.*e}", precision, v)).map_err(|_| Error::FormatError)?;
+        let (sign, mantissa, exp) = split_exp_notation(&buf);
+        let mut digits = [0u8; 17];
+        let mut len = 0;
+        for &digit in mantissa.iter().filter(|&&c| c != b'.') {
+            digits[len] = digit;
+            len += 1;
+        }
+        while len > 1 && digits[len - 1] == b'0' {
+            len -= 1;
+        }
+        self.output.write(sign)?;
+        Ok(write_significant(&mut self.output, &digits[..len], exp)?)
+    }
+    /// Write the opening bracket of an array or an object.
+    ///
+    /// Arrays of up to [`PrettyConfig::inline_arrays`] elements open a single line.
+    fn begin_compound(&mut self, open: u8, len: Option<usize>) -> Result<(), W::Error> {
+        if let Some(pretty) = self.pretty.as_mut() {
+            pretty.depth += 1;
+            let inline = open == b'[' && len.is_some_and(|len| len <= pretty.config.inline_len);
+            if pretty.inline != 0 || inline {
+                pretty.inline += 1;
+            }
+        }
+        Ok(self.output.write_byte(open)?)
     }
-}
-
-#[inline(always)]
-fn hex_4bit(c: u8) -> u8 {
-    if c <= 9 {
-        0x30 + c
-    } else {
-        0x41 + (c - 10)
+    /// Write the separator preceding an array element or an object member.
+    fn begin_item(&mut self, first: bool) -> Result<(), W::Error> {
+        if !first {
+            self.output.write_byte(b',')?;
+        }
+        match self.pretty {
+            Some(Pretty { inline: 0, .. }) => self.write_newline(),
+            Some(_) if !first => Ok(self.output.write_byte(b' ')?),
+            _ => Ok(())
+        }
+    }
+    /// Write the separator between an object key and its value.
+    fn begin_value(&mut self) -> Result<(), W::Error> {
+        let sep: &[u8] = if self.pretty.is_some() { b": " } else { b":" };
+        Ok(self.output.write(sep)?)
+    }
+    /// Write the closing bracket of an array or an object, `empty` if nothing was written inside.
+    fn end_compound(&mut self, close: u8, empty: bool) -> Result<(), W::Error> {
+        if let Some(pretty) = self.pretty.as_mut() {
+            pretty.depth -= 1;
+            if pretty.inline != 0 {
+                pretty.inline -= 1;
+            }
+            else if !empty {
+                self.write_newline()?;
+            }
+        }
+        Ok(self.output.write_byte(close)?)
+    }
+    /// Write a line break followed by the indentation of the current nesting level.
+    fn write_newline(&mut self) -> Result<(), W::Error> {
+        const SPACES: [u8; 32] = [b' '; 32];
+        const TABS: [u8; 32] = [b'\t'; 32];
+        let Some(Pretty { config, depth, .. }) = self.pretty else {
+            return Ok(())
+        };
+        self.output.write(match config.newline {
+            Newline::Lf => b"\n",
+            Newline::CrLf => b"\r\n"
+        })?;
+        let (fill, width) = match config.indent {
+            Indent::Spaces(width) => (&SPACES, width),
+            Indent::Tabs(width) => (&TABS, width)
+        };
+        let mut size = depth * usize::from(width);
+        while size != 0 {
+            let chunk = size.min(fill.len());
+            self.output.write(&fill[..chunk])?;
+            size -= chunk;
+        }
+        Ok(())
     }
-}
-
-/// Upper-case hex for value in 0..256, encoded as ASCII bytes
-#[inline(always)]
-fn hex(c: u8) -> [u8;2] {
-    [hex_4bit(c >> 4), hex_4bit(c & 0x0F)]
 }
 
 macro_rules! serialize_unsigned {
@@ -434,19 +921,31 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::Serializer for &'a mut Serializer<W,
         serialize_unsigned!(self, 20, v)
     }
 
+    fn serialize_i128(self, v: i128) -> Result<(), W::Error> {
+        // "-170141183460469231731687303715884105728"
+        serialize_signed!(self, 40, v, i128, u128)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, W::Error> {
+        // "340282366920938463463374607431768211455"
+        serialize_unsigned!(self, 39, v)
+    }
+
     fn serialize_f32(self, v: f32) -> Result<(), W::Error> {
-        if v.is_finite() {
-            serialize_ryu!(self, v)
-        } else {
-            self.serialize_none()
+        match self.float_format {
+            _ if !v.is_finite() => self.serialize_non_finite(f64::from(v)),
+            FloatFormat::Shortest => serialize_ryu!(self, v),
+            FloatFormat::Fixed(decimals) => self.serialize_float_fixed(f64::from(v), decimals),
+            FloatFormat::Significant(digits) => self.serialize_float_significant(f64::from(v), digits)
         }
     }
 
     fn serialize_f64(self, v: f64) -> Result<(), W::Error> {
-        if v.is_finite() {
-            serialize_ryu!(self, v)
-        } else {
-            self.serialize_none()
+        match self.float_format {
+            _ if !v.is_finite() => self.serialize_non_finite(v),
+            FloatFormat::Shortest => serialize_ryu!(self, v),
+            FloatFormat::Fixed(decimals) => self.serialize_float_fixed(v, decimals),
+            FloatFormat::Significant(digits) => self.serialize_float_significant(v, digits)
         }
     }
 
@@ -457,8 +956,9 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::Serializer for &'a mut Serializer<W,
     }
 
     fn serialize_str(self, v: &str) -> Result<(), W::Error> {
+        self.output.reserve(v.len() + 2)?;
         self.output.write_byte(b'"')?;
-        format_escaped_str_contents(&mut self.output, v)?;
+        format_escaped_str_contents(&mut self.output, v, self.escape)?;
         Ok(self.output.write_byte(b'"')?)
     }
 
@@ -513,28 +1013,29 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::Serializer for &'a mut Serializer<W,
     where
         T: ?Sized + Serialize,
     {
-        self.output.write_byte(b'{')?;
+        self.begin_compound(b'{', None)?;
+        self.begin_item(true)?;
         self.serialize_str(variant)?;
-        self.output.write_byte(b':')?;
+        self.begin_value()?;
         value.serialize(&mut *self)?;
-        Ok(self.output.write_byte(b'}')?)
+        self.end_compound(b'}', false)
     }
 
-    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, W::Error> {
-        self.output.write_byte(b'[')?;
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, W::Error> {
+        self.begin_compound(b'[', len)?;
         Ok(SeqMapSerializer { first: true, ser: self })
     }
 
-    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, W::Error> {
-        self.serialize_seq(None)
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, W::Error> {
+        self.serialize_seq(Some(len))
     }
 
     fn serialize_tuple_struct(
         self,
         _name: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeTupleStruct, W::Error> {
-        self.serialize_seq(None)
+        self.serialize_seq(Some(len))
     }
 
     fn serialize_tuple_variant(
@@ -542,17 +1043,19 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::Serializer for &'a mut Serializer<W,
         _name: &'static str,
         _variant_index: u32,
         variant: &'static str,
-        _len: usize,
+        len: usize,
     ) -> Result<Self::SerializeTupleVariant, W::Error> {
-        self.output.write_byte(b'{')?;
+        self.begin_compound(b'{', None)?;
+        self.begin_item(true)?;
         self.serialize_str(variant)?;
-        self.output.write(b":[")?;
+        self.begin_value()?;
+        self.begin_compound(b'[', Some(len))?;
         Ok(SeqMapSerializer { first: true, ser: self })
     }
 
     // Maps are represented in JSON as `{ K: V, K: V, ... }`.
-    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, W::Error> {
-        self.output.write_byte(b'{')?;
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, W::Error> {
+        self.begin_compound(b'{', len)?;
         Ok(SeqMapSerializer { first: true, ser: self })
     }
 
@@ -573,9 +1076,11 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::Serializer for &'a mut Serializer<W,
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant, W::Error> {
-        self.output.write_byte(b'{')?;
+        self.begin_compound(b'{', None)?;
+        self.begin_item(true)?;
         self.serialize_str(variant)?;
-        self.output.write(b":{")?;
+        self.begin_value()?;
+        self.begin_compound(b'{', None)?;
         Ok(SeqMapSerializer { first: true, ser: self })
     }
 
@@ -583,7 +1088,7 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::Serializer for &'a mut Serializer<W,
         where T: fmt::Display + ?Sized
     {
         self.output.write_byte(b'"')?;
-        let mut col = StringCollector::new(&mut self.output);
+        let mut col = StringCollector::new(&mut self.output).with_escape(self.escape);
         fmt::write(&mut col, format_args!("{}", value)).map_err(|_| Error::FormatError)?;
         Ok(self.output.write_byte(b'"')?)
     }
@@ -656,6 +1161,14 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::Serializer for KeySer<'a, W, B>
         self.quote(|ser| ser.serialize_u64(v))
     }
 
+    fn serialize_i128(self, v: i128) -> Result<(), W::Error> {
+        self.quote(|ser| ser.serialize_i128(v))
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, W::Error> {
+        self.quote(|ser| ser.serialize_u128(v))
+    }
+
     fn serialize_f32(self, _v: f32) -> Result<(), W::Error> {
         Err(Error::InvalidKeyType)
     }
@@ -791,6 +1304,7 @@ pub struct SeqMapSerializer<'a, W, B> {
 /// [`Serializer::collect_str`]: ser::Serializer::collect_str
 pub struct StringCollector<'a, W> {
     output: &'a mut W,
+    escape: EscapeConfig
 }
 
 impl<'a, W> StringCollector<'a, W> {
@@ -798,13 +1312,18 @@ impl<'a, W> StringCollector<'a, W> {
     /// should implement [`SerWrite`].
     #[inline(always)]
     pub fn new(output: &'a mut W) -> Self {
-        Self { output }
+        Self { output, escape: EscapeConfig::new() }
+    }
+    /// Set the string escaping options.
+    #[inline(always)]
+    pub fn with_escape(self, escape: EscapeConfig) -> Self {
+        Self { escape, ..self }
     }
 }
 
 impl<'a, W: SerWrite> fmt::Write for StringCollector<'a, W> {
     fn write_str(&mut self, s: &str) -> fmt::Result {
-        format_escaped_str_contents(self.output, s).map_err(|_| fmt::Error)
+        format_escaped_str_contents(self.output, s, self.escape).map_err(|_| fmt::Error)
     }
 }
 
@@ -819,17 +1338,13 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::SerializeSeq for SeqMapSerializer<'a,
     fn serialize_element<T>(&mut self, value: &T) -> Result<(), W::Error>
         where T: ?Sized + Serialize
     {
-        if self.first {
-            self.first = false;
-        }
-        else {
-            self.ser.output.write_byte(b',')?;
-        }
+        self.ser.begin_item(self.first)?;
+        self.first = false;
         value.serialize(&mut *self.ser)
     }
 
     fn end(self) -> Result<(), W::Error> {
-        Ok(self.ser.output.write_byte(b']')?)
+        self.ser.end_compound(b']', self.first)
     }
 }
 
@@ -842,17 +1357,13 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::SerializeTuple for SeqMapSerializer<'
     fn serialize_element<T>(&mut self, value: &T) -> Result<(), W::Error>
     where T: ?Sized + Serialize
     {
-        if self.first {
-            self.first = false;
-        }
-        else {
-            self.ser.output.write_byte(b',')?;
-        }
+        self.ser.begin_item(self.first)?;
+        self.first = false;
         value.serialize(&mut *self.ser)
     }
 
     fn end(self) -> Result<(), W::Error> {
-        Ok(self.ser.output.write_byte(b']')?)
+        self.ser.end_compound(b']', self.first)
     }
 }
 
@@ -865,17 +1376,13 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::SerializeTupleStruct for SeqMapSerial
     fn serialize_field<T>(&mut self, value: &T) -> Result<(), W::Error>
         where T: ?Sized + Serialize
     {
-        if self.first {
-            self.first = false;
-        }
-        else {
-            self.ser.output.write_byte(b',')?;
-        }
+        self.ser.begin_item(self.first)?;
+        self.first = false;
         value.serialize(&mut *self.ser)
     }
 
     fn end(self) -> Result<(), W::Error> {
-        Ok(self.ser.output.write_byte(b']')?)
+        self.ser.end_compound(b']', self.first)
     }
 }
 
@@ -889,17 +1396,14 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::SerializeTupleVariant for SeqMapSeria
     fn serialize_field<T>(&mut self, value: &T) -> Result<(), W::Error>
     where T: ?Sized + Serialize
     {
-        if self.first {
-            self.first = false;
-        }
-        else {
-            self.ser.output.write_byte(b',')?;
-        }
+        self.ser.begin_item(self.first)?;
+        self.first = false;
         value.serialize(&mut *self.ser)
     }
 
     fn end(self) -> Result<(), W::Error> {
-        Ok(self.ser.output.write(b"]}")?)
+        self.ser.end_compound(b']', self.first)?;
+        self.ser.end_compound(b'}', false)
     }
 }
 
@@ -915,24 +1419,20 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::SerializeMap for SeqMapSerializer<'a,
     fn serialize_key<T>(&mut self, key: &T) -> Result<(), W::Error>
         where T: ?Sized + Serialize
     {
-        if self.first {
-            self.first = false;
-        }
-        else {
-            self.ser.output.write_byte(b',')?;
-        }
+        self.ser.begin_item(self.first)?;
+        self.first = false;
         key.serialize(KeySer { ser: self.ser })
     }
 
     fn serialize_value<T>(&mut self, value: &T) -> Result<(), W::Error>
     where T: ?Sized + Serialize
     {
-        self.ser.output.write(b":")?;
+        self.ser.begin_value()?;
         value.serialize(&mut *self.ser)
     }
 
     fn end(self) -> Result<(), W::Error> {
-        Ok(self.ser.output.write_byte(b'}')?)
+        self.ser.end_compound(b'}', self.first)
     }
 }
 
@@ -945,19 +1445,15 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::SerializeStruct for SeqMapSerializer<
     fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), W::Error>
         where T: ?Sized + Serialize
     {
-        if self.first {
-            self.first = false;
-        }
-        else {
-            self.ser.output.write_byte(b',')?;
-        }
+        self.ser.begin_item(self.first)?;
+        self.first = false;
         key.serialize(&mut *self.ser)?;
-        self.ser.output.write(b":")?;
+        self.ser.begin_value()?;
         value.serialize(&mut *self.ser)
     }
 
     fn end(self) -> Result<(), W::Error> {
-        Ok(self.ser.output.write_byte(b'}')?)
+        self.ser.end_compound(b'}', self.first)
     }
 }
 
@@ -970,52 +1466,121 @@ impl<'a, W: SerWrite, B: ByteEncoder> ser::SerializeStructVariant for SeqMapSeri
     fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), W::Error>
         where T: ?Sized + Serialize
     {
-        if self.first {
-            self.first = false;
-        }
-        else {
-            self.ser.output.write_byte(b',')?;
-        }
+        self.ser.begin_item(self.first)?;
+        self.first = false;
         key.serialize(&mut *self.ser)?;
-        self.ser.output.write(b":")?;
+        self.ser.begin_value()?;
         value.serialize(&mut *self.ser)
     }
 
     fn end(self) -> Result<(), W::Error> {
-        Ok(self.ser.output.write(b"}}")?)
+        self.ser.end_compound(b'}', self.first)?;
+        self.ser.end_compound(b'}', false)
+    }
+}
+
+/// Forwards formatted floats to the underlying writer, keeping its error.
+struct FmtWriter<'a, W: SerWrite> {
+    output: &'a mut W,
+    error: Option<W::Error>
+}
+
+impl<'a, W: SerWrite> fmt::Write for FmtWriter<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.output.write_str(s).map_err(|err| {
+            self.error = Some(err);
+            fmt::Error
+        })
+    }
+}
+
+/// Split a float formatted with `{:e}` into the sign, the mantissa and the exponent.
+fn split_exp_notation(formatted: &[u8]) -> (&[u8], &[u8], &[u8]) {
+    let (sign, rest) = formatted.split_at(usize::from(formatted.first() == Some(&b'-')));
+    let pos = rest.iter().position(|&c| c == b'e').unwrap_or(rest.len());
+    let (mantissa, exp) = rest.split_at(pos);
+    (sign, mantissa, exp.get(1..).unwrap_or_default())
+}
+
+/// Write significant `digits` of a number with the decimal exponent `exp`.
+///
+/// Like the shortest representation, uses the exponential notation only for
+/// numbers below `1e-7` or from `1e21` up.
+fn write_significant<W>(writer: &mut W, digits: &[u8], exp: &[u8]) -> core::result::Result<(), W::Error>
+    where W: ?Sized + SerWrite
+{
+    const ZEROS: &[u8; 21] = b"000000000000000000000";
+    let (negative, exp) = match exp.split_first() {
+        Some((b'-', exp)) => (true, exp),
+        _ => (false, exp)
+    };
+    let abs = exp.iter().fold(0usize, |acc, &c| acc * 10 + usize::from(c - b'0'));
+    if !negative && abs < 21 {
+        let int_len = abs + 1;
+        if digits.len() <= int_len {
+            writer.write_vectored(&[digits, &ZEROS[..int_len - digits.len()]])
+        }
+        else {
+            let (int, frac) = digits.split_at(int_len);
+            writer.write_vectored(&[int, b".", frac])
+        }
+    }
+    else if negative && abs <= 7 {
+        writer.write_vectored(&[b"0.", &ZEROS[..abs - 1], digits])
+    }
+    else {
+        let (first, rest) = digits.split_at(1);
+        let dot: &[u8] = if rest.is_empty() { b"" } else { b"." };
+        let sign: &[u8] = if negative { b"-" } else { b"+" };
+        writer.write_vectored(&[first, dot, rest, b"e", sign, exp])
     }
 }
 
 fn format_escaped_str_contents<W>(
     writer: &mut W,
     value: &str,
+    escape: EscapeConfig
 ) -> Result<(), W::Error>
     where W: ?Sized + SerWrite
 {
     let bytes = value.as_bytes();
 
     let mut start = 0;
-
-    for (i, &byte) in bytes.iter().enumerate() {
-        let escape = match byte {
-            0x00..=0x1F => ESCAPE[byte as usize],
-            QU|BS => byte,
-            _ => continue
+    let mut index = 0;
+    // up to 2 `\uXXXX` escape sequences of a surrogate pair
+    let mut sequence = [0u8; 12];
+
+    while let Some(&byte) = bytes.get(index) {
+        let mut size = 1;
+        let seq_len = match byte {
+            0x00..=0x1F => match ESCAPE[byte as usize] {
+                UU => unicode_escape(&mut sequence, &[u16::from(byte)]),
+                escape => short_escape(&mut sequence, escape)
+            },
+            QU|BS => short_escape(&mut sequence, byte),
+            b'/' if escape.slash => short_escape(&mut sequence, byte),
+            b'<'|b'>'|b'&'|b'\'' if escape.html => unicode_escape(&mut sequence, &[u16::from(byte)]),
+            // the first byte of a multi-byte character: U+2028 and U+2029 start with 0xE2
+            0x80.. if escape.ascii || (escape.html && byte == 0xE2) => {
+                let ch = value[index..].chars().next().unwrap_or_default();
+                size = ch.len_utf8();
+                if escape.ascii || matches!(ch, '\u{2028}'|'\u{2029}') {
+                    unicode_escape(&mut sequence, ch.encode_utf16(&mut [0; 2]))
+                }
+                else {
+                    0
+                }
+            }
+            _ => 0
         };
 
-        if start < i {
-            writer.write_str(&value[start..i])?;
-        }
-
-        if escape == UU {
-            writer.write(b"\\u00")?;
-            writer.write(&hex(byte))?;
-        }
-        else {
-            writer.write(&[b'\\', escape])?;
+        if seq_len != 0 {
+            // write the unescaped run together with the escape sequence
+            writer.write_vectored(&[&bytes[start..index], &sequence[..seq_len]])?;
+            start = index + size;
         }
 
-        start = i + 1;
+        index += size;
     }
 
     if start == bytes.len() {
@@ -1025,6 +1590,25 @@ fn format_escaped_str_contents<W>(
     Ok(writer.write_str(&value[start..])?)
 }
 
+/// Write a `\c` escape sequence to `sequence` and return its length.
+#[inline(always)]
+fn short_escape(sequence: &mut [u8; 12], escape: u8) -> usize {
+    sequence[..2].copy_from_slice(&[b'\\', escape]);
+    2
+}
+
+/// Write `\uXXXX` escape sequences of UTF-16 `units` to `sequence` and return their length.
+#[inline]
+fn unicode_escape(sequence: &mut [u8; 12], units: &[u16]) -> usize {
+    for (target, unit) in sequence.chunks_exact_mut(6).zip(units) {
+        let [hi, lo] = unit.to_be_bytes();
+        let [h1, h2] = encode_byte(hi);
+        let [l1, l2] = encode_byte(lo);
+        target.copy_from_slice(&[b'\\', b'u', h1, h2, l1, l2]);
+    }
+    units.len() * 6
+}
+
 const BB: u8 = b'b'; // \x08
 const TT: u8 = b't'; // \x09
 const NN: u8 = b'n'; // \x0A
@@ -1261,7 +1845,7 @@ mod tests {
     fn test_json_map() {
         #[derive(Debug, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
         struct Wrap(bool);
-        let mut buf = [0u8;68];
+        let mut buf = [0u8;112];
         macro_rules! test_map_with_key_int {
             ($($ty:ty),*) => {$(
                 let mut amap = BTreeMap::<$ty,&str>::new();
@@ -1278,7 +1862,7 @@ mod tests {
                 assert_eq!(to_str(&mut buf, &amap).unwrap(), &expected);
             )*};
         }
-        test_map_with_key_int!(i8, u8, i16, u16, i32, u32, i64, u64);
+        test_map_with_key_int!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128);
         let mut amap = BTreeMap::<&str,i32>::new();
         amap.insert("key", 118);
         let expected = r#"{"key":118}"#;
@@ -1888,6 +2472,466 @@ mod tests {
         assert_eq!(to_str(&mut buf, "__"), Err(Error::Writer(SerError::BufferFull)));
     }
 
+    #[test]
+    fn test_ser_serialized_size() {
+        #[derive(Serialize)]
+        struct Test<'a> {
+            #[serde(with = "serde_bytes")]
+            key: &'a[u8],
+            text: &'a str
+        }
+        let mut buf = [0u8;64];
+        let value = Test { key: b"\x00\xff", text: "\n\"ℝ" };
+        assert_eq!(serialized_size(&value).unwrap(), to_str(&mut buf, &value).unwrap().len());
+        assert_eq!(serialized_size(&value).unwrap(), 32);
+        assert_eq!(serialized_size_with_encoder::<HexStrByteEncoder, _>(&value).unwrap(),
+                   to_str_hex_bytes(&mut buf, &value).unwrap().len());
+        assert_eq!(serialized_size_with_encoder::<Base64ByteEncoder, _>(&value).unwrap(),
+                   to_str_base64_bytes(&mut buf, &value).unwrap().len());
+        assert_eq!(serialized_size(&()).unwrap(), 4);
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn test_ser_window() {
+        #[derive(Serialize)]
+        struct Test<'a> {
+            #[serde(with = "serde_bytes")]
+            key: &'a[u8],
+            text: &'a str
+        }
+        let value = Test { key: b"\x00\xff", text: "\n\"ℝ" };
+        let mut buf = [0u8;64];
+        let expected = to_str(&mut buf, &value).unwrap().as_bytes().to_vec();
+        let mut out = Vec::new();
+        let mut page = [0u8;7];
+        loop {
+            let window = to_window(&mut page, out.len(), &value).unwrap();
+            assert_eq!(window.total_len, expected.len());
+            out.extend_from_slice(window.data);
+            if !window.full { break }
+        }
+        assert_eq!(out, expected);
+        let window = to_window_with_encoder::<HexStrByteEncoder, _>(&mut page, 7, &value).unwrap();
+        assert_eq!(window.data, br#""00FF","#);
+        assert_eq!(window.total_len, 31);
+        assert!(window.full);
+    }
+
+    #[test]
+    fn test_ser_chain() {
+        let mut a = [0u8;8];
+        let mut b = [0u8;8];
+        let mut c = [0u8;10];
+        let mut segments: [&mut [u8]; 3] = [&mut a, &mut b, &mut c];
+        let mut writer = ser_write::ChainWriter::new(&mut segments);
+        to_writer(&mut writer, &[("Hello", 1), ("World", 2)]).unwrap();
+        assert_eq!(writer.segments_used(), 3);
+        assert_eq!(writer.last_segment_len(), 9);
+        assert_eq!(to_writer(&mut writer, &"!!!!"), Err(Error::Writer(SerError::BufferFull)));
+        assert_eq!(&a, br#"[["Hello"#);
+        assert_eq!(&b, br#"",1],["W"#);
+        assert_eq!(&c[..9], br#"orld",2]]"#);
+    }
+
+    #[test]
+    fn test_ser_dyn_writer() {
+        let mut buf = [0u8;16];
+        let mut writer = SliceWriter::new(&mut buf);
+        to_dyn_writer(&mut writer, &[1, 2, 3]).unwrap();
+        to_dyn_writer_with_encoder::<HexStrByteEncoder, _>(&mut writer, serde_bytes::Bytes::new(b"\xff")).unwrap();
+        assert_eq!(writer.as_ref(), br#"[1,2,3]"FF""#);
+        assert_eq!(to_dyn_writer(&mut writer, &"Hello"), Err(Error::Writer(SerError::BufferFull)));
+        let mut writer = ser_write::DynWriter::new(&mut writer);
+        assert_eq!(to_dyn_writer(&mut writer, &"Hello"), Err(Error::Writer(SerError::WriterFailed)));
+        assert_eq!(writer.take_error(), Some(SerError::BufferFull));
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn test_ser_limit_writer() {
+        let mut vec = Vec::new();
+        let mut writer = ser_write::LimitWriter::new(&mut vec, 19);
+        to_writer(&mut writer, &["Hello", "World!"]).unwrap();
+        assert_eq!(writer.remaining(), 1);
+        assert_eq!(to_writer(&mut writer, &()), Err(Error::Writer(ser_write::LimitError::Exceeded { limit: 19 })));
+        assert_eq!(vec, br#"["Hello","World!"]"#);
+    }
+
+    #[test]
+    fn test_ser_vectored_writes() {
+        let mut buf = [0u8;16];
+        let mut writer = ser_write::test_util::CallRecorder::new(&mut buf);
+        to_writer(&mut writer, "a\"b\x01c").unwrap();
+        assert_eq!(writer.writer.as_ref(), br#""a\"b\u0001c""#);
+        assert_eq!((writer.writes, writer.vectored, writer.reserved), (3, 2, 7));
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc", feature = "arrayvec", feature = "heapless"))]
+    #[test]
+    fn test_ser_string_containers() {
+        #[derive(Serialize)]
+        struct Test<'a> {
+            name: &'a str,
+            #[serde(with = "serde_bytes")]
+            data: &'a [u8]
+        }
+        let test = Test { name: "Zażółć\n", data: b"\x01" };
+        let expected = r#"{"name":"Zażółć\n","data":[1]}"#;
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        {
+            let mut string = String::new();
+            to_writer(&mut string, &test).unwrap();
+            assert_eq!(string, expected);
+            string.clear();
+            let invalid = Test { name: "", data: b"\xFF" };
+            assert_eq!(to_writer_pass_bytes(&mut string, &invalid), Err(Error::Writer(SerError::InvalidUtf8)));
+        }
+        #[cfg(feature = "heapless")]
+        {
+            assert_eq!(to_heapless_string::<40,_>(&test).unwrap(), expected);
+            assert_eq!(to_heapless_string::<33,_>(&test), Err(Error::Writer(SerError::BufferFull)));
+        }
+        #[cfg(feature = "arrayvec")]
+        {
+            assert_eq!(&to_array_string::<40,_>(&test).unwrap(), expected);
+            assert_eq!(to_array_string::<33,_>(&test), Err(Error::Writer(SerError::BufferFull)));
+        }
+    }
+
+    #[test]
+    fn test_ser_lzss_roundtrip() {
+        use ser_write::lzss::{LzssWriter, LzssDecoder};
+        #[derive(Debug, PartialEq, Serialize, serde::Deserialize)]
+        struct Reading<'a> {
+            id: u32,
+            temp: f32,
+            #[serde(borrow)]
+            status: [&'a str; 2]
+        }
+        let readings = [
+            Reading { id: 1, temp: 21.5, status: ["ok", "ok"] },
+            Reading { id: 2, temp: 21.5, status: ["ok", "warn"] },
+            Reading { id: 3, temp: 21.25, status: ["ok", "ok"] }];
+        let mut buf = [0u8;128];
+        let mut writer = LzssWriter::<_, 128>::new(SliceWriter::new(&mut buf));
+        to_writer(&mut writer, &readings).unwrap();
+        let compressed = writer.finish().unwrap().split().0;
+        assert!(compressed.len() < 100);
+        let mut dbuf = [0u8;150];
+        let mut writer = SliceWriter::new(&mut dbuf);
+        let mut decoder = LzssDecoder::<128>::new();
+        decoder.feed(compressed, &mut writer).unwrap();
+        decoder.finish().unwrap();
+        let json = writer.split().0;
+        assert_eq!(json.len(), serialized_size(&readings).unwrap());
+        let decoded: [Reading<'_>; 3] = crate::from_mut_slice(json).unwrap();
+        assert_eq!(decoded, readings);
+    }
+
+    #[test]
+    fn test_ser_pretty() {
+        #[derive(Serialize)]
+        enum Shape {
+            Point,
+            Circle(u8),
+            Line(u8, u8),
+            Rect { w: u8, h: u8 }
+        }
+        #[derive(Serialize)]
+        struct Config<'a> {
+            name: &'a str,
+            pins: [u8; 3],
+            #[serde(with = "serde_bytes")]
+            key: &'a [u8],
+            empty: [u8; 0],
+            nested: ((), [[i8; 2]; 2]),
+            shapes: [Shape; 4],
+            unit: Empty
+        }
+        #[derive(Serialize)]
+        struct Empty {}
+        let config = Config {
+            name: "fan",
+            pins: [1, 2, 3],
+            key: &[0xde, 0xad],
+            empty: [],
+            nested: ((), [[-1, 1], [2, -2]]),
+            shapes: [Shape::Point, Shape::Circle(5), Shape::Line(1, 2), Shape::Rect { w: 3, h: 4 }],
+            unit: Empty {}
+        };
+        let mut buf = [0u8;512];
+        let mut writer = SliceWriter::new(&mut buf);
+        to_writer_pretty(&mut writer, PrettyConfig::new(), &config).unwrap();
+        assert_eq!(core::str::from_utf8(writer.as_ref()).unwrap(), r#"{
+  "name": "fan",
+  "pins": [
+    1,
+    2,
+    3
+  ],
+  "key": [
+    222,
+    173
+  ],
+  "empty": [],
+  "nested": [
+    null,
+    [
+      [
+        -1,
+        1
+      ],
+      [
+        2,
+        -2
+      ]
+    ]
+  ],
+  "shapes": [
+    "Point",
+    {
+      "Circle": 5
+    },
+    {
+      "Line": [
+        1,
+        2
+      ]
+    },
+    {
+      "Rect": {
+        "w": 3,
+        "h": 4
+      }
+    }
+  ],
+  "unit": {}
+}"#);
+        let mut compact = [0u8;256];
+        let compact_len = serialized_size(&config).unwrap();
+        to_writer(SliceWriter::new(&mut compact), &config).unwrap();
+        assert!(writer.as_ref().iter().filter(|c| !c.is_ascii_whitespace()).eq(&compact[..compact_len]));
+
+        let config_inline = PrettyConfig::default()
+            .indent(Indent::Tabs(1))
+            .newline(Newline::CrLf)
+            .inline_arrays(3);
+        writer.clear();
+        to_writer_pretty_with_encoder::<HexStrByteEncoder, _, _>(&mut writer, config_inline, &config).unwrap();
+        assert_eq!(core::str::from_utf8(writer.as_ref()).unwrap(),
+            "{\r\n\t\"name\": \"fan\",\r\n\t\"pins\": [1, 2, 3],\r\n\t\"key\": \"DEAD\",\r\n\t\"empty\": [],\r\n\
+             \t\"nested\": [null, [[-1, 1], [2, -2]]],\r\n\t\"shapes\": [\r\n\t\t\"Point\",\r\n\
+             \t\t{\r\n\t\t\t\"Circle\": 5\r\n\t\t},\r\n\t\t{\r\n\t\t\t\"Line\": [1, 2]\r\n\t\t},\r\n\
+             \t\t{\r\n\t\t\t\"Rect\": {\r\n\t\t\t\t\"w\": 3,\r\n\t\t\t\t\"h\": 4\r\n\t\t\t}\r\n\t\t}\r\n\t],\r\n\
+             \t\"unit\": {}\r\n}");
+
+        writer.clear();
+        let config_wide = PrettyConfig::new().indent(Indent::Spaces(40)).inline_arrays(usize::MAX);
+        to_writer_pretty(&mut writer, config_wide, &[("a", [(); 1]), ("b", [(); 1])]).unwrap();
+        assert_eq!(core::str::from_utf8(writer.as_ref()).unwrap(), r#"[["a", [null]], ["b", [null]]]"#);
+        writer.clear();
+        to_writer_pretty(&mut writer, config_wide, &Shape::Circle(1)).unwrap();
+        assert_eq!(writer.as_ref().len(), 1 + 1 + 40 + 11 + 1 + 1);
+        writer.clear();
+        to_writer_pretty_with_encoder::<PassThroughByteEncoder, _, _>(&mut writer, config_wide, &serde_bytes::Bytes::new(b"[1,2]")).unwrap();
+        assert_eq!(writer.as_ref(), b"[1,2]");
+        let mut buf = [0u8;3];
+        let mut writer = SliceWriter::new(&mut buf);
+        assert_eq!(to_writer_pretty(&mut writer, PrettyConfig::new(), &[1]), Err(Error::Writer(SerError::BufferFull)));
+    }
+
+    #[test]
+    fn test_ser_non_finite() {
+        #[derive(Debug, Serialize, serde::Deserialize)]
+        struct Sensor {
+            temp: f32,
+            hum: f64,
+            fault: [f64; 2]
+        }
+        let sensor = Sensor { temp: f32::NAN, hum: f64::INFINITY, fault: [f64::NEG_INFINITY, 1.5] };
+        let mut buf = [0u8;64];
+        for (non_finite, expected) in [
+            (NonFinite::Null, r#"{"temp":null,"hum":null,"fault":[null,1.5]}"#),
+            (NonFinite::String, r#"{"temp":"NaN","hum":"Infinity","fault":["-Infinity",1.5]}"#),
+            (NonFinite::Json5, r#"{"temp":NaN,"hum":Infinity,"fault":[-Infinity,1.5]}"#)
+        ] {
+            let mut ser = SerializerByteArray::new(SliceWriter::new(&mut buf))
+                .with_non_finite(non_finite);
+            sensor.serialize(&mut ser).unwrap();
+            let json = ser.into_inner().split().0;
+            assert_eq!(core::str::from_utf8(json).unwrap(), expected);
+            let decoded: Sensor = crate::from_mut_slice(json).unwrap();
+            assert!(decoded.temp.is_nan());
+            assert_eq!(decoded.fault[1], 1.5);
+            if non_finite != NonFinite::Null {
+                assert_eq!(decoded.hum, f64::INFINITY);
+                assert_eq!(decoded.fault[0], f64::NEG_INFINITY);
+            }
+        }
+        let mut ser = SerializerByteArray::new(SliceWriter::new(&mut buf))
+            .with_non_finite(NonFinite::Error);
+        assert_eq!(sensor.serialize(&mut ser), Err(Error::NonFiniteFloat));
+        let mut ser = SerializerByteArray::new(SliceWriter::new(&mut buf))
+            .with_non_finite(NonFinite::Error);
+        [1.5f32, -0.0].serialize(&mut ser).unwrap();
+        assert_eq!(ser.writer_ref().as_ref(), b"[1.5,0]");
+        let mut ser = SerializerByteArray::new_pretty(SliceWriter::new(&mut buf), PrettyConfig::new().inline_arrays(1))
+            .with_non_finite(NonFinite::Json5);
+        [f32::NEG_INFINITY].serialize(&mut ser).unwrap();
+        assert_eq!(ser.writer_ref().as_ref(), b"[-Infinity]");
+        let mut writer = SliceWriter::new(&mut buf);
+        fmt::write(&mut writer, format_args!("{}", Error::<SerError>::NonFiniteFloat)).unwrap();
+        assert_eq!(writer.as_ref(), b"non-finite float can't be serialized to JSON");
+    }
+
+    #[test]
+    fn test_ser_float_format() {
+        fn to_str_with<'a, T: Serialize + ?Sized>(buf: &'a mut [u8], format: FloatFormat, value: &T) -> &'a str {
+            let mut ser = SerializerByteArray::new(SliceWriter::new(buf))
+                .with_float_format(format);
+            value.serialize(&mut ser).unwrap();
+            core::str::from_utf8(ser.into_inner().split().0).unwrap()
+        }
+        let mut buf = [0u8;400];
+        let temp = 21.3f32;
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Shortest, &temp), "21.3");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Shortest, &f64::from(temp)), "21.299999237060547");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Fixed(2), &f64::from(temp)), "21.30");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Fixed(0), &[0.5f64, 1.5, 2.5, -0.49]), "[0,2,2,-0]");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Fixed(3), &[1.0005f64, 1.00051, 1e-9]), "[1.000,1.001,0.000]");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Fixed(1), &1e21f64), "1000000000000000000000.0");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Fixed(1), &f64::MAX).len(), 311);
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Significant(3), &f64::from(temp)), "21.3");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Significant(3), &[1.0f32, 0.0, -0.0]), "[1,0,-0]");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Significant(2), &[12345.0f64, -0.00012345, 99.9]),
+                   "[12000,-0.00012,100]");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Significant(4), &[1.0005f64, 1.00051]), "[1,1.001]");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Significant(3), &[1.5e-7f64, 1.25e-8, 9.999e20, 1e21, -1.23456e300]),
+                   "[0.00000015,1.25e-8,1e+21,1e+21,-1.23e+300]");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Significant(0), &[1.5f64, 2.5]), "[2,2]");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Significant(20), &[0.1f64, 5e-324, -f64::MAX]),
+                   "[0.10000000000000001,4.9406564584124654e-324,-1.7976931348623157e+308]");
+        assert_eq!(to_str_with(&mut buf, FloatFormat::Significant(9), &[0.1f32, f32::MAX]), "[0.100000001,3.40282347e+38]");
+        let mut ser = SerializerByteArray::new(SliceWriter::new(&mut buf))
+            .with_float_format(FloatFormat::Significant(1))
+            .with_non_finite(NonFinite::Json5);
+        [f32::NAN, 0.25].serialize(&mut ser).unwrap();
+        assert_eq!(ser.writer_ref().as_ref(), b"[NaN,0.2]");
+        for format in [FloatFormat::Fixed(6), FloatFormat::Significant(8)] {
+            let mut buf = [0u8;8];
+            let mut ser = SerializerByteArray::new(SliceWriter::new(&mut buf))
+                .with_float_format(format);
+            assert_eq!(123.456789f64.serialize(&mut ser), Err(Error::Writer(SerError::BufferFull)));
+        }
+    }
+
+    #[test]
+    fn test_ser_escape() {
+        fn to_str_with<'a, T: Serialize + ?Sized>(buf: &'a mut [u8], escape: EscapeConfig, value: &T) -> &'a str {
+            let mut ser = SerializerByteArray::new(SliceWriter::new(buf))
+                .with_escape(escape);
+            value.serialize(&mut ser).unwrap();
+            core::str::from_utf8(ser.into_inner().split().0).unwrap()
+        }
+        struct Display;
+        impl Serialize for Display {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+                serializer.collect_str("</ä>")
+            }
+        }
+        #[derive(Serialize)]
+        struct Page<'a> {
+            #[serde(rename = "<ä/>")]
+            tag: &'a str,
+            display: Display
+        }
+        let page = Page { tag: "a/b & 'c' \u{2028}é\u{2029}\n🎉", display: Display };
+        let mut buf = [0u8;256];
+        assert_eq!(to_str_with(&mut buf, EscapeConfig::default(), &page),
+            "{\"<ä/>\":\"a/b & 'c' \u{2028}é\u{2029}\\n🎉\",\"display\":\"</ä>\"}");
+        assert_eq!(to_str_with(&mut buf, EscapeConfig::new().ascii(true), &page),
+            r#"{"<\u00E4/>":"a/b & 'c' \u2028\u00E9\u2029\n\uD83C\uDF89","display":"</\u00E4>"}"#);
+        assert_eq!(to_str_with(&mut buf, EscapeConfig::new().html(true), &page),
+            r#"{"\u003Cä/\u003E":"a/b \u0026 \u0027c\u0027 \u2028é\u2029\n🎉","display":"\u003C/ä\u003E"}"#);
+        assert_eq!(to_str_with(&mut buf, EscapeConfig::new().slash(true), &page),
+            "{\"<ä\\/>\":\"a\\/b & 'c' \u{2028}é\u{2029}\\n🎉\",\"display\":\"<\\/ä>\"}");
+        let all = EscapeConfig::new().ascii(true).html(true).slash(true);
+        let json = to_str_with(&mut buf, all, &page);
+        assert_eq!(json,
+            r#"{"\u003C\u00E4\/\u003E":"a\/b \u0026 \u0027c\u0027 \u2028\u00E9\u2029\n\uD83C\uDF89","display":"\u003C\/\u00E4\u003E"}"#);
+        let len = json.len();
+        #[derive(Debug, PartialEq, serde::Deserialize)]
+        struct PageDe<'a> {
+            #[serde(rename = "<ä/>")]
+            tag: &'a str,
+            display: &'a str
+        }
+        let decoded: PageDe<'_> = crate::from_mut_slice(&mut buf[..len]).unwrap();
+        assert_eq!(decoded, PageDe { tag: page.tag, display: "</ä>" });
+        let mut buf = [0u8;11];
+        let mut writer = SliceWriter::new(&mut buf);
+        let mut ser = SerializerByteArray::new(&mut writer).with_escape(all);
+        assert_eq!("ab🎉".serialize(&mut ser), Err(Error::Writer(SerError::BufferFull)));
+        assert_eq!(writer.as_ref(), b"\"ab");
+    }
+
+    #[test]
+    fn test_ser_int128() {
+        #[derive(Debug, PartialEq, Serialize, serde::Deserialize)]
+        struct Record {
+            uuid: u128,
+            counter: i128,
+            tags: [(i128, u128); 2]
+        }
+        let record = Record {
+            uuid: u128::MAX,
+            counter: i128::MIN,
+            tags: [(-1, 0), (i128::MAX, u128::from(u64::MAX) + 1)]
+        };
+        let mut buf = [0u8;256];
+        let expected = r#"{"uuid":340282366920938463463374607431768211455,"counter":-170141183460469231731687303715884105728,"tags":[[-1,0],[170141183460469231731687303715884105727,18446744073709551616]]}"#;
+        assert_eq!(to_str(&mut buf, &record).unwrap(), expected);
+        let len = expected.len();
+        assert_eq!(crate::from_mut_slice::<Record>(&mut buf[..len]), Ok(record));
+        let mut writer = SliceWriter::new(&mut buf);
+        let mut ser = SerializerByteArray::new(&mut writer);
+        let mut map = ser::Serializer::serialize_map(&mut ser, Some(2)).unwrap();
+        ser::SerializeMap::serialize_entry(&mut map, &i128::MIN, &u128::MAX).unwrap();
+        ser::SerializeMap::serialize_entry(&mut map, &u128::MAX, &0i128).unwrap();
+        ser::SerializeMap::end(map).unwrap();
+        assert_eq!(writer.as_ref(), br#"{"-170141183460469231731687303715884105728":340282366920938463463374607431768211455,"340282366920938463463374607431768211455":0}"#);
+        let mut buf = [0u8;39];
+        let mut writer = SliceWriter::new(&mut buf);
+        assert_eq!(to_writer(&mut writer, &i128::MIN), Err(Error::Writer(SerError::BufferFull)));
+    }
+
+    #[test]
+    fn test_ser_atomic() {
+        let mut buf = [0u8;16];
+        let mut writer = SliceWriter::new(&mut buf);
+        to_writer_atomic(&mut writer, &[1,2,3]).unwrap();
+        assert_eq!(to_writer_atomic(&mut writer, &[100,200,300]), Err(Error::Writer(SerError::BufferFull)));
+        assert_eq!(writer.as_ref(), b"[1,2,3]");
+        to_writer_atomic_with_encoder::<HexStrByteEncoder, _, _>(&mut writer, &serde_bytes::Bytes::new(b"\xAB")).unwrap();
+        assert_eq!(writer.as_ref(), b"[1,2,3]\"AB\"");
+        assert_eq!(to_writer_atomic(&mut writer, &serde_bytes::Bytes::new(b"\xAB\xCD")), Err(Error::Writer(SerError::BufferFull)));
+        assert_eq!(writer.as_ref(), b"[1,2,3]\"AB\"");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_ser_io_writer() {
+        use ser_write::IoWriter;
+        let mut buf = [0u8;10];
+        let mut writer = IoWriter::new(std::io::Cursor::new(&mut buf[..]));
+        to_writer(&mut writer, &[1,2,3]).unwrap();
+        assert_eq!(writer.0.position(), 7);
+        let err = to_writer(&mut writer, "xxxx").unwrap_err();
+        assert!(matches!(err, Error::Writer(e) if e.kind() == std::io::ErrorKind::WriteZero));
+        assert_eq!(&buf, b"[1,2,3]\"xx");
+    }
+
     #[cfg(any(feature = "std", feature = "alloc"))]
     #[test]
     fn test_ser_error_string() {
@@ -1927,4 +2971,73 @@ mod tests {
         assert_eq!(res, b"foo bar\xe2\x84\x9d\\t\xc3\xa4\\u0000\\n");
         assert_eq!(writer.capacity(), 0);
     }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_ser_async() {
+        use core::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+        use ser_write::{AsyncSerWrite, AsyncSerError};
+
+        fn block_on<F: Future>(fut: F) -> F::Output {
+            let mut fut = pin!(fut);
+            let mut cx = Context::from_waker(Waker::noop());
+            loop {
+                if let Poll::Ready(res) = fut.as_mut().poll(&mut cx) {
+                    break res
+                }
+            }
+        }
+
+        struct Sink<'a>(SliceWriter<'a>);
+
+        impl AsyncSerWrite for Sink<'_> {
+            type Error = SerError;
+            async fn write(&mut self, buf: &[u8]) -> ser_write::SerResult<()> {
+                self.0.write(buf)
+            }
+        }
+
+        #[derive(Serialize)]
+        struct Test<'a> {
+            #[serde(with = "serde_bytes")]
+            key: &'a[u8]
+        }
+        let value = Test { key: b"\x00\x01" };
+        let mut out = [0u8;16];
+        let mut sink = Sink(SliceWriter::new(&mut out));
+        let mut buf = [0u8;16];
+        assert_eq!(block_on(to_writer_async(&mut sink, &mut buf, &value)), Ok(()));
+        assert_eq!(sink.0.as_ref(), br#"{"key":[0,1]}"#);
+        sink.0.clear();
+        assert_eq!(block_on(to_writer_async_with_encoder::<HexStrByteEncoder,_,_>(&mut sink, &mut buf, &value)), Ok(()));
+        assert_eq!(sink.0.as_ref(), br#"{"key":"0001"}"#);
+        sink.0.clear();
+        assert_eq!(block_on(to_writer_async(&mut sink, &mut buf[..3], &value)), Ok(()));
+        assert_eq!(sink.0.as_ref(), br#"{"key":[0,1]}"#);
+        sink.0.clear();
+        struct FloatKey;
+        impl serde::Serialize for FloatKey {
+            fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+                where S: serde::Serializer
+            {
+                use serde::ser::SerializeMap;
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(&0.5f32, &0)?;
+                map.end()
+            }
+        }
+        assert_eq!(block_on(to_writer_async(&mut sink, &mut buf[..1], &FloatKey)),
+            Err(AsyncSerError::Serialize(Error::InvalidKeyType)));
+        assert!(sink.0.is_empty());
+        let mut sink = Sink(SliceWriter::new(&mut out[..12]));
+        assert_eq!(block_on(to_writer_async(&mut sink, &mut buf, &value)),
+            Err(AsyncSerError::Writer(SerError::BufferFull)));
+        let mut out = [0u8;32];
+        let mut writer = ser_write::AsyncBufWriter::<_, 16>::new(Sink(SliceWriter::new(&mut out)));
+        for value in [1u8, 2, 3, 4, 5, 6] {
+            assert_eq!(block_on(writer.write_with(|w| to_writer(w, &[value; 2]))), Ok(()));
+        }
+        let sink = block_on(writer.finish()).unwrap();
+        assert_eq!(sink.0.as_ref(), b"[1,1][2,2][3,3][4,4][5,5][6,6]");
+    }
 }
@@ -79,7 +79,26 @@ pub use ser::{
 pub use ser::{
     to_writer_compact,
     to_writer,
-    to_writer_named
+    to_writer_named,
+    to_writer_atomic_compact,
+    to_writer_atomic,
+    to_writer_atomic_named,
+    serialized_size_compact,
+    serialized_size,
+    serialized_size_named,
+    to_window_compact,
+    to_window,
+    to_window_named,
+    to_dyn_writer_compact,
+    to_dyn_writer,
+    to_dyn_writer_named
+};
+
+#[cfg(feature = "async")]
+pub use ser::{
+    to_writer_async_compact,
+    to_writer_async,
+    to_writer_async_named
 };
 
 pub use de::{
@@ -12,7 +12,9 @@ use ser::Serializer as _;
 
 use super::magick::*;
 
-use ser_write::SerWrite;
+use ser_write::{SerWrite, SerCheckpoint, CountingWriter, DynSerWrite, Window, WindowWriter};
+#[cfg(feature = "async")]
+use ser_write::{AsyncSerWrite, AsyncSerError, SerError};
 
 /// MessagePack serializer serializing structs to arrays and enum variants as indexes.
 ///
@@ -99,6 +101,238 @@ pub fn to_writer_named<W, T>(writer: W, value: &T) -> Result<(), W::Error>
     value.serialize(&mut serializer)
 }
 
+/// Serialize `value` as a MessagePack message to a [`DynSerWrite`] trait object.
+///
+/// Serialize data structures as arrays without field names and enum variants as indexes.
+///
+/// See [`to_dyn_writer`] for details.
+///
+/// **Warning**: with this function only last fields can be skipped from a data structure.
+pub fn to_dyn_writer_compact<T>(writer: &mut dyn DynSerWrite, value: &T) -> Result<(), ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    to_writer_compact(writer, value)
+}
+
+/// Serialize `value` as a MessagePack message to a [`DynSerWrite`] trait object.
+///
+/// Serialize data structures as maps with field and enum variants as indexes.
+///
+/// The serializer is compiled once for all writers instead of once per writer type.
+/// Writers with error types other than [`ser_write::SerError`] can be
+/// wrapped in [`ser_write::DynWriter`].
+pub fn to_dyn_writer<T>(writer: &mut dyn DynSerWrite, value: &T) -> Result<(), ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    to_writer(writer, value)
+}
+
+/// Serialize `value` as a MessagePack message to a [`DynSerWrite`] trait object.
+///
+/// Serialize data structures as maps where resulting message will contain field and enum variant names.
+///
+/// See [`to_dyn_writer`] for details.
+pub fn to_dyn_writer_named<T>(writer: &mut dyn DynSerWrite, value: &T) -> Result<(), ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    to_writer_named(writer, value)
+}
+
+/// Return the exact length of `value` serialized as a MessagePack message.
+///
+/// Serialize data structures as arrays without field names and enum variants as indexes.
+///
+/// The value is serialized without storing the output anywhere.
+pub fn serialized_size_compact<T>(value: &T) -> Result<usize, ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    let mut counter = CountingWriter::new();
+    to_writer_compact(&mut counter, value)?;
+    Ok(counter.count())
+}
+
+/// Return the exact length of `value` serialized as a MessagePack message.
+///
+/// Serialize data structures as maps with field and enum variants as indexes.
+///
+/// The value is serialized without storing the output anywhere.
+pub fn serialized_size<T>(value: &T) -> Result<usize, ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    let mut counter = CountingWriter::new();
+    to_writer(&mut counter, value)?;
+    Ok(counter.count())
+}
+
+/// Return the exact length of `value` serialized as a MessagePack message.
+///
+/// Serialize data structures as maps where resulting message will contain field and enum variant names.
+///
+/// The value is serialized without storing the output anywhere.
+pub fn serialized_size_named<T>(value: &T) -> Result<usize, ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    let mut counter = CountingWriter::new();
+    to_writer_named(&mut counter, value)?;
+    Ok(counter.count())
+}
+
+/// Serialize `value` as a MessagePack message and capture only the bytes of the output
+/// in the range `[offset, offset + buf.len())` to `buf`.
+///
+/// Serialize data structures as arrays without field names and enum variants as indexes.
+///
+/// See [`to_window`] for details.
+///
+/// **Warning**: with this function only last fields can be skipped from a data structure.
+pub fn to_window_compact<'a, T>(buf: &'a mut [u8], offset: usize, value: &T) -> Result<Window<'a>, ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    let mut writer = WindowWriter::new(buf, offset);
+    to_writer_compact(&mut writer, value)?;
+    Ok(writer.finish())
+}
+
+/// Serialize `value` as a MessagePack message and capture only the bytes of the output
+/// in the range `[offset, offset + buf.len())` to `buf`.
+///
+/// Serialize data structures as maps with field and enum variants as indexes.
+///
+/// The returned [`Window`] contains the total length of the output and indicates
+/// whether the window has been filled completely. Repeat with the `offset` advanced
+/// by the length of the captured data until the window is not full to transmit
+/// the whole message in pages.
+///
+/// # Panics
+/// Panics if `buf` is empty.
+pub fn to_window<'a, T>(buf: &'a mut [u8], offset: usize, value: &T) -> Result<Window<'a>, ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    let mut writer = WindowWriter::new(buf, offset);
+    to_writer(&mut writer, value)?;
+    Ok(writer.finish())
+}
+
+/// Serialize `value` as a MessagePack message and capture only the bytes of the output
+/// in the range `[offset, offset + buf.len())` to `buf`.
+///
+/// Serialize data structures as maps where resulting message will contain field and enum variant names.
+///
+/// See [`to_window`] for details.
+pub fn to_window_named<'a, T>(buf: &'a mut [u8], offset: usize, value: &T) -> Result<Window<'a>, ser_write::SerError>
+    where T: Serialize + ?Sized
+{
+    let mut writer = WindowWriter::new(buf, offset);
+    to_writer_named(&mut writer, value)?;
+    Ok(writer.finish())
+}
+
+/// Serialize `value` as a MessagePack message to a [`SerCheckpoint`] implementation.
+///
+/// Serialize data structures as arrays without field names and enum variants as indexes.
+///
+/// If the serialization fails, e.g. with [`Error::FieldSkipped`], the `writer` is left
+/// exactly as it was before the call.
+pub fn to_writer_atomic_compact<W, T>(writer: &mut W, value: &T) -> Result<(), W::Error>
+    where W: SerCheckpoint + ?Sized,
+          <W as SerWrite>::Error: fmt::Display+fmt::Debug,
+          T: Serialize + ?Sized
+{
+    ser_write::write_atomic(writer, |tx| to_writer_compact(tx, value))
+}
+
+/// Serialize `value` as a MessagePack message to a [`SerCheckpoint`] implementation.
+///
+/// Serialize data structures as maps with field and enum variants as indexes.
+///
+/// If the serialization fails, the `writer` is left exactly as it was before the call.
+pub fn to_writer_atomic<W, T>(writer: &mut W, value: &T) -> Result<(), W::Error>
+    where W: SerCheckpoint + ?Sized,
+          <W as SerWrite>::Error: fmt::Display+fmt::Debug,
+          T: Serialize + ?Sized
+{
+    ser_write::write_atomic(writer, |tx| to_writer(tx, value))
+}
+
+/// Serialize `value` as a MessagePack message to a [`SerCheckpoint`] implementation.
+///
+/// Serialize data structures as maps where resulting message will contain field and enum variant names.
+///
+/// If the serialization fails, the `writer` is left exactly as it was before the call.
+pub fn to_writer_atomic_named<W, T>(writer: &mut W, value: &T) -> Result<(), W::Error>
+    where W: SerCheckpoint + ?Sized,
+          <W as SerWrite>::Error: fmt::Display+fmt::Debug,
+          T: Serialize + ?Sized
+{
+    ser_write::write_atomic(writer, |tx| to_writer_named(tx, value))
+}
+
+/// Serialize `value` as a MessagePack message to an [`AsyncSerWrite`] implementation.
+///
+/// Serialize data structures as arrays without field names and enum variants as indexes.
+///
+/// See [`to_writer_async`] for details.
+///
+/// **Warning**: with this function only last fields can be skipped from a data structure.
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub async fn to_writer_async_compact<W, T>(
+        writer: W,
+        buf: &mut [u8],
+        value: &T
+    ) -> core::result::Result<(), AsyncSerError<Error<SerError>, W::Error>>
+    where W: AsyncSerWrite,
+          T: Serialize + ?Sized
+{
+    ser_write::write_async_with(writer, buf, |stage| to_writer_compact(stage, value)).await
+}
+
+/// Serialize `value` as a MessagePack message to an [`AsyncSerWrite`] implementation.
+///
+/// Serialize data structures as maps with field and enum variants as indexes.
+///
+/// The message is streamed to the `writer` in chunks of up to `buf.len()` bytes. The `value`
+/// is serialized once for each chunk, see [`write_async_with`] for details. To serialize
+/// many values once each, pass [`to_writer`] to [`AsyncBufWriter::write_with`] instead.
+///
+/// The returned future is pending whenever the `writer` applies backpressure.
+///
+/// # Panics
+/// Panics if `buf` is empty.
+///
+/// [`AsyncBufWriter::write_with`]: ser_write::AsyncBufWriter::write_with
+/// [`write_async_with`]: ser_write::write_async_with
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub async fn to_writer_async<W, T>(
+        writer: W,
+        buf: &mut [u8],
+        value: &T
+    ) -> core::result::Result<(), AsyncSerError<Error<SerError>, W::Error>>
+    where W: AsyncSerWrite,
+          T: Serialize + ?Sized
+{
+    ser_write::write_async_with(writer, buf, |stage| to_writer(stage, value)).await
+}
+
+/// Serialize `value` as a MessagePack message to an [`AsyncSerWrite`] implementation.
+///
+/// Serialize data structures as maps where resulting message will contain field and enum variant names.
+///
+/// See [`to_writer_async`] for details.
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub async fn to_writer_async_named<W, T>(
+        writer: W,
+        buf: &mut [u8],
+        value: &T
+    ) -> core::result::Result<(), AsyncSerError<Error<SerError>, W::Error>>
+    where W: AsyncSerWrite,
+          T: Serialize + ?Sized
+{
+    ser_write::write_async_with(writer, buf, |stage| to_writer_named(stage, value)).await
+}
+
 /// Serializing error
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[non_exhaustive]
@@ -421,21 +655,17 @@ impl<'a, W: SerWrite> ser::Serializer for &'a mut $serializer<W>
     fn serialize_bytes(self, v: &[u8]) -> Result<(), W::Error> {
         let size = v.len();
         if let Ok(size) = u8::try_from(size) {
-            self.output.write_byte(BIN_8)?;
-            self.output.write_byte(size)?;
+            write_data_header(&mut self.output, BIN_8, &[size], v.len(), v)
         }
         else if let Ok(size) = u16::try_from(size) {
-            self.output.write_byte(BIN_16)?;
-            self.output.write(&size.to_be_bytes())?;
+            write_data_header(&mut self.output, BIN_16, &size.to_be_bytes(), v.len(), v)
         }
         else if let Ok(size) = u32::try_from(size) {
-            self.output.write_byte(BIN_32)?;
-            self.output.write(&size.to_be_bytes())?;
+            write_data_header(&mut self.output, BIN_32, &size.to_be_bytes(), v.len(), v)
         }
         else {
-            return Err(Error::DataLength)
+            Err(Error::DataLength)
         }
-        Ok(self.output.write(v)?)
     }
 
     fn serialize_none(self) -> Result<(), W::Error> {
@@ -567,7 +797,7 @@ impl<'a, W: SerWrite> ser::Serializer for &'a mut $serializer<W>
         let mut col = StringLenCounter(0);
         fmt::write(&mut col, format_args!("{}", value)).map_err(|_| Error::FormatError)?;
         let StringLenCounter(len) = col;
-        write_str_len(&mut self.output, len)?;
+        write_str_with_data(&mut self.output, len, &[])?;
         let mut col = StringCollector::new(len, &mut self.output);
         fmt::write(&mut col, format_args!("{}", value)).map_err(|_| Error::FormatError)
     }
@@ -602,70 +832,74 @@ fn write_u32<W: SerWrite>(output: &mut W, v: u32) -> Result<(), W::Error> {
 
 #[inline]
 fn write_str<W: SerWrite>(output: &mut W, v: &str) -> Result<(), W::Error> {
-    let size = v.len();
-    write_str_len(output, size)?;
-    Ok(output.write_str(v)?)
+    write_str_with_data(output, v.len(), v.as_bytes())
 }
 
+/// Write a str header for `len` bytes followed by `data` with a single vectored write
 #[inline]
-fn write_str_len<W: SerWrite>(output: &mut W, len: usize) -> Result<(), W::Error> {
+fn write_str_with_data<W: SerWrite>(output: &mut W, len: usize, data: &[u8]) -> Result<(), W::Error> {
+    let size = len;
     if len <= MAX_FIXSTR_SIZE {
-        output.write_byte(FIXSTR | (len as u8))?;
+        write_data_header(output, FIXSTR | (len as u8), &[], size, data)
     }
     else if let Ok(len) = u8::try_from(len) {
-        output.write_byte(STR_8)?;
-        output.write_byte(len)?;
+        write_data_header(output, STR_8, &[len], size, data)
     }
     else if let Ok(len) = u16::try_from(len) {
-        output.write_byte(STR_16)?;
-        output.write(&len.to_be_bytes())?;
+        write_data_header(output, STR_16, &len.to_be_bytes(), size, data)
     }
     else if let Ok(len) = u32::try_from(len) {
-        output.write_byte(STR_32)?;
-        output.write(&len.to_be_bytes())?;
+        write_data_header(output, STR_32, &len.to_be_bytes(), size, data)
     }
     else {
-        return Err(Error::StrLength)
+        Err(Error::StrLength)
     }
-    Ok(())
 }
 
 #[inline]
 fn write_array_len<W: SerWrite>(output: &mut W, len: usize) -> Result<(), W::Error> {
     if len <= MAX_FIXARRAY_SIZE {
-        output.write_byte(FIXARRAY | (len as u8))?;
+        Ok(output.write_byte(FIXARRAY | (len as u8))?)
     }
     else if let Ok(len) = u16::try_from(len) {
-        output.write_byte(ARRAY_16)?;
-        output.write(&len.to_be_bytes())?;
+        write_header(output, ARRAY_16, &len.to_be_bytes(), &[])
     }
     else if let Ok(len) = u32::try_from(len) {
-        output.write_byte(ARRAY_32)?;
-        output.write(&len.to_be_bytes())?;
+        write_header(output, ARRAY_32, &len.to_be_bytes(), &[])
     }
     else {
-        return Err(Error::SeqLength)
+        Err(Error::SeqLength)
     }
-    Ok(())
 }
 
 #[inline]
 fn write_map_len<W: SerWrite>(output: &mut W, len: usize) -> Result<(), W::Error> {
     if len <= MAX_FIXMAP_SIZE {
-        output.write_byte(FIXMAP | (len as u8))?;
+        Ok(output.write_byte(FIXMAP | (len as u8))?)
     }
     else if let Ok(len) = u16::try_from(len) {
-        output.write_byte(MAP_16)?;
-        output.write(&len.to_be_bytes())?;
+        write_header(output, MAP_16, &len.to_be_bytes(), &[])
     }
     else if let Ok(len) = u32::try_from(len) {
-        output.write_byte(MAP_32)?;
-        output.write(&len.to_be_bytes())?;
+        write_header(output, MAP_32, &len.to_be_bytes(), &[])
     }
     else {
-        return Err(Error::MapLength)
+        Err(Error::MapLength)
     }
-    Ok(())
+}
+
+/// Reserve room for a whole str or bin item with `size` bytes of payload and write its header
+/// followed by `data`, which may be only the beginning of the payload
+#[inline]
+fn write_data_header<W: SerWrite>(output: &mut W, marker: u8, len: &[u8], size: usize, data: &[u8]) -> Result<(), W::Error> {
+    output.reserve(size.saturating_add(1 + len.len()))?;
+    write_header(output, marker, len, data)
+}
+
+/// Write a `marker` followed by big-endian `len` bytes and `data` with a single vectored write
+#[inline]
+fn write_header<W: SerWrite>(output: &mut W, marker: u8, len: &[u8], data: &[u8]) -> Result<(), W::Error> {
+    Ok(output.write_vectored(&[core::slice::from_ref(&marker), len, data])?)
 }
 
 #[cfg(not(any(feature = "std", feature = "alloc")))]
@@ -2042,11 +2276,11 @@ mod tests {
     fn test_ser_str_array_map_oversize_errors() {
         let mut writer = SliceWriter::new(&mut []);
         let oversize = usize::try_from(u32::MAX).unwrap();
-        assert_eq!(write_str_len(&mut writer, oversize), Err(Error::from(SerError::BufferFull)));
+        assert_eq!(write_str_with_data(&mut writer, oversize, &[]), Err(Error::from(SerError::BufferFull)));
         assert_eq!(write_array_len(&mut writer, oversize), Err(Error::from(SerError::BufferFull)));
         assert_eq!(write_map_len(&mut writer, oversize), Err(Error::from(SerError::BufferFull)));
         let oversize = oversize.checked_add(1).unwrap();
-        assert_eq!(write_str_len(&mut writer, oversize), Err(Error::StrLength));
+        assert_eq!(write_str_with_data(&mut writer, oversize, &[]), Err(Error::StrLength));
         assert_eq!(write_array_len(&mut writer, oversize), Err(Error::SeqLength));
         assert_eq!(write_map_len(&mut writer, oversize), Err(Error::MapLength));
     }
@@ -2089,6 +2323,116 @@ mod tests {
         assert_eq!(a1, a2);
     }
 
+    #[test]
+    fn test_ser_serialized_size() {
+        #[derive(Serialize)]
+        struct Test {
+            compact: bool,
+            schema: u32
+        }
+        let value = Test { compact: true, schema: 0 };
+        assert_eq!(serialized_size_compact(&value), Ok(3));
+        assert_eq!(serialized_size(&value), Ok(5));
+        assert_eq!(serialized_size_named(&value), Ok(18));
+        assert_eq!(serialized_size(&[u64::MAX; 16]), Ok(3 + 9*16));
+        assert_eq!(serialized_size(&""), Ok(1));
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn test_ser_window() {
+        #[derive(Serialize)]
+        struct Test {
+            compact: bool,
+            schema: u32,
+        }
+        let value = Test { compact: true, schema: 0 };
+        let mut page = [0u8;4];
+        let window = to_window_compact(&mut page, 0, &value).unwrap();
+        assert_eq!(window, Window { data: &mut b"\x92\xC3\x00".clone(), total_len: 3, full: false });
+        let window = to_window(&mut page, 2, &value).unwrap();
+        assert_eq!(window, Window { data: &mut b"\xC3\x01\x00".clone(), total_len: 5, full: false });
+        let mut out = Vec::new();
+        loop {
+            let window = to_window_named(&mut page, out.len(), &value).unwrap();
+            assert_eq!(window.total_len, 18);
+            out.extend_from_slice(window.data);
+            if !window.full { break }
+        }
+        assert_eq!(out, b"\x82\xA7compact\xC3\xA6schema\x00");
+    }
+
+    #[test]
+    fn test_ser_dyn_writer() {
+        #[derive(Serialize)]
+        struct Test {
+            compact: bool,
+            schema: u32,
+        }
+        let value = Test { compact: true, schema: 0 };
+        let mut buf = [0u8;26];
+        let mut writer = SliceWriter::new(&mut buf);
+        to_dyn_writer_compact(&mut writer, &value).unwrap();
+        to_dyn_writer(&mut writer, &value).unwrap();
+        to_dyn_writer_named(&mut writer, &value).unwrap();
+        assert_eq!(writer.as_ref(), b"\x92\xC3\x00\x82\x00\xC3\x01\x00\x82\xA7compact\xC3\xA6schema\x00");
+        assert_eq!(to_dyn_writer(&mut writer, &value), Err(Error::Writer(SerError::BufferFull)));
+    }
+
+    #[test]
+    fn test_ser_vectored_writes() {
+        let mut buf = [0u8;16];
+        let mut writer = ser_write::test_util::CallRecorder::new(&mut buf);
+        to_writer(&mut writer, &("abc", serde_bytes::Bytes::new(b"xyz"))).unwrap();
+        assert_eq!(writer.writer.as_ref(), b"\x92\xA3abc\xC4\x03xyz");
+        assert_eq!((writer.writes, writer.vectored, writer.reserved), (1, 2, 9));
+    }
+
+    #[test]
+    fn test_ser_atomic() {
+        #[derive(Serialize)]
+        struct Property {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            description: Option<&'static str>,
+            value: Option<u32>,
+        }
+        let mut buf = [0u8;16];
+        let mut writer = SliceWriter::new(&mut buf);
+        let property = Property { description: None, value: Some(0) };
+        to_writer_atomic(&mut writer, &property).unwrap();
+        assert_eq!(writer.as_ref(), b"\x81\x01\x00");
+        assert_eq!(to_writer_atomic_compact(&mut writer, &property), Err(Error::FieldSkipped));
+        assert_eq!(writer.as_ref(), b"\x81\x01\x00");
+        to_writer_atomic_named(&mut writer, &property).unwrap();
+        assert_eq!(writer.as_ref(), b"\x81\x01\x00\x81\xA5value\x00");
+        assert_eq!(to_writer_atomic_named(&mut writer, &property), Err(Error::Writer(SerError::BufferFull)));
+        assert_eq!(writer.as_ref(), b"\x81\x01\x00\x81\xA5value\x00");
+        let property = Property { description: Some("x"), value: None };
+        to_writer_atomic_compact(&mut writer, &property).unwrap();
+        assert_eq!(writer.as_ref(), b"\x81\x01\x00\x81\xA5value\x00\x92\xA1x\xC0");
+    }
+
+    #[test]
+    fn test_ser_framed() {
+        use ser_write::{cobs, slip, CobsWriter, SlipWriter};
+        let value = (0u32, "\u{c0}\u{db}", [0xC0u8, 0xDB, 0]);
+        let mut buf = [0u8;32];
+        let mut writer = CobsWriter::new(SliceWriter::new(&mut buf));
+        to_writer_compact(&mut writer, &value).unwrap();
+        let frame = writer.finish().unwrap().split().0;
+        assert!(!frame[..frame.len() - 1].contains(&0));
+        let (res, len): ((u32, &str, [u8;3]), _) = crate::from_slice(cobs::decode_in_place(frame).unwrap()).unwrap();
+        assert_eq!(len, 13);
+        assert_eq!(res, value);
+        let mut writer = SlipWriter::new(SliceWriter::new(&mut buf));
+        to_writer_compact(&mut writer, &value).unwrap();
+        let frame = writer.finish().unwrap().split().0;
+        assert_eq!(frame.iter().filter(|&&b| b == slip::END).count(), 1);
+        let (res, len): ((u32, &str, [u8;3]), _) = crate::from_slice(slip::decode_in_place(frame).unwrap()).unwrap();
+        assert_eq!(len, 13);
+        assert_eq!(res, value);
+    }
+
     #[cfg(any(feature = "std", feature = "alloc"))]
     #[test]
     fn test_ser_error_string() {
@@ -2113,4 +2457,67 @@ mod tests {
         write!(writer, "{}", custom).unwrap();
         assert_eq!(writer.as_ref(), b"error while serializing MessagePack");
     }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_ser_async() {
+        use core::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+        use ser_write::{AsyncSerWrite, AsyncSerError};
+
+        fn block_on<F: Future>(fut: F) -> F::Output {
+            let mut fut = pin!(fut);
+            let mut cx = Context::from_waker(Waker::noop());
+            loop {
+                if let Poll::Ready(res) = fut.as_mut().poll(&mut cx) {
+                    break res
+                }
+            }
+        }
+
+        struct Sink<'a>(SliceWriter<'a>);
+
+        impl AsyncSerWrite for Sink<'_> {
+            type Error = SerError;
+            async fn write(&mut self, buf: &[u8]) -> ser_write::SerResult<()> {
+                self.0.write(buf)
+            }
+        }
+
+        #[derive(Serialize)]
+        struct Test {
+            compact: bool,
+            schema: u32
+        }
+        let value = Test { compact: true, schema: 0 };
+        let mut out = [0u8;18];
+        let mut sink = Sink(SliceWriter::new(&mut out));
+        let mut buf = [0u8;18];
+        assert_eq!(block_on(to_writer_async_compact(&mut sink, &mut buf, &value)), Ok(()));
+        assert_eq!(sink.0.as_ref(), b"\x92\xC3\x00");
+        sink.0.clear();
+        assert_eq!(block_on(to_writer_async(&mut sink, &mut buf, &value)), Ok(()));
+        assert_eq!(sink.0.as_ref(), b"\x82\x00\xC3\x01\x00");
+        sink.0.clear();
+        assert_eq!(block_on(to_writer_async_named(&mut sink, &mut buf, &value)), Ok(()));
+        assert_eq!(sink.0.as_ref(), b"\x82\xA7compact\xC3\xA6schema\x00");
+        sink.0.clear();
+        assert_eq!(block_on(to_writer_async_named(&mut sink, &mut buf[..4], &value)), Ok(()));
+        assert_eq!(sink.0.as_ref(), b"\x82\xA7compact\xC3\xA6schema\x00");
+        sink.0.clear();
+        struct PhonySeq;
+        impl serde::Serialize for PhonySeq {
+            fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+                where S: serde::Serializer
+            {
+                serializer.serialize_seq(None)?;
+                unreachable!();
+            }
+        }
+        assert_eq!(block_on(to_writer_async(&mut sink, &mut buf[..1], &PhonySeq)),
+            Err(AsyncSerError::Serialize(Error::SeqLength)));
+        assert!(sink.0.is_empty());
+        let mut sink = Sink(SliceWriter::new(&mut out[..17]));
+        assert_eq!(block_on(to_writer_async_named(&mut sink, &mut buf, &value)),
+            Err(AsyncSerError::Writer(SerError::BufferFull)));
+    }
 }
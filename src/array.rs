@@ -0,0 +1,201 @@
+//! An owned fixed-capacity array writer.
+use core::ops::Deref;
+
+use super::*;
+
+/// An owned writer storing up to `N` bytes in an array.
+///
+/// Unlike [`SliceWriter`] it doesn't borrow its buffer, so it can be easily stored
+/// in structs or returned from functions.
+///
+/// A write either succeeds entirely or returns [`SerError::BufferFull`] without
+/// writing anything.
+#[derive(Clone)]
+pub struct ArrayWriter<const N: usize> {
+    buf: [u8; N],
+    len: usize
+}
+
+impl<const N: usize> ArrayWriter<N> {
+    /// Create a new, empty instance
+    pub const fn new() -> Self {
+        ArrayWriter { buf: [0; N], len: 0 }
+    }
+    /// Return populated length
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Return whether the output is not populated.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Return total capacity of the container
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+    /// Return remaining free capacity
+    #[inline]
+    pub fn rem_capacity(&self) -> usize {
+        N - self.len
+    }
+    /// Reset cursor to the beginning of the array
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+    /// Return the populated portion of the array as a string slice.
+    ///
+    /// Return an error if it's not a valid UTF-8.
+    #[inline]
+    pub fn as_str(&self) -> Result<&str, core::str::Utf8Error> {
+        core::str::from_utf8(self.as_ref())
+    }
+    /// Split the array and return the populated portion of it together with
+    /// a [`SliceWriter`] over the remaining free capacity.
+    pub fn split(&mut self) -> (&mut [u8], SliceWriter<'_>) {
+        let (res, buf) = self.buf.split_at_mut(self.len);
+        (res, SliceWriter::new(buf))
+    }
+    /// Destruct self returning the array and the populated length.
+    #[inline]
+    pub fn into_inner(self) -> ([u8; N], usize) {
+        (self.buf, self.len)
+    }
+}
+
+impl<const N: usize> Default for ArrayWriter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Debug for ArrayWriter<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArrayWriter")
+         .field("buf", &self.as_ref())
+         .field("capacity", &N)
+         .finish()
+    }
+}
+
+impl<const N: usize> PartialEq for ArrayWriter<N> {
+    /// Compares only the populated portions
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl<const N: usize> Eq for ArrayWriter<N> {}
+
+impl<const N: usize> AsRef<[u8]> for ArrayWriter<N> {
+    /// Returns a populated portion of the array
+    fn as_ref(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl<const N: usize> AsMut<[u8]> for ArrayWriter<N> {
+    /// Returns a populated portion of the array
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.buf[..self.len]
+    }
+}
+
+impl<const N: usize> Deref for ArrayWriter<N> {
+    type Target = [u8];
+
+    /// Returns a populated portion of the array
+    fn deref(&self) -> &[u8] {
+        self.as_ref()
+    }
+}
+
+impl<const N: usize> SerWrite for ArrayWriter<N> {
+    type Error = SerError;
+
+    fn write(&mut self, buf: &[u8]) -> SerResult<()> {
+        let end = self.len + buf.len();
+        match self.buf.get_mut(self.len..end) {
+            Some(chunk) => {
+                chunk.copy_from_slice(buf);
+                self.len = end;
+                Ok(())
+            }
+            None => Err(SerError::BufferFull)
+        }
+    }
+}
+
+impl<const N: usize> SerCheckpoint for ArrayWriter<N> {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.len = self.len.min(checkpoint);
+    }
+}
+
+impl<const N: usize> fmt::Write for ArrayWriter<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        SerWrite::write_str(self, s).map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+    use super::*;
+
+    fn hello() -> ArrayWriter<22> {
+        let mut writer = ArrayWriter::new();
+        writer.write(b"Hello World!").unwrap();
+        writer
+    }
+
+    #[test]
+    fn test_array_writer() {
+        let mut writer = hello();
+        assert_eq!(writer.capacity(), 22);
+        assert_eq!(writer.rem_capacity(), 10);
+        assert_eq!(writer.len(), 12);
+        assert!(!writer.is_empty());
+        writer.write_byte(b' ').unwrap();
+        let word = "Bye";
+        write!(writer, "Good {}!", word).unwrap();
+        assert_eq!(&*writer, b"Hello World! Good Bye!");
+        assert_eq!(writer.as_str(), Ok("Hello World! Good Bye!"));
+        assert_eq!(writer.rem_capacity(), 0);
+        assert_eq!(writer.write_byte(b'!'), Err(SerError::BufferFull));
+        assert_eq!(write!(writer, "!"), Err(fmt::Error));
+        writer.as_mut()[0] = b'J';
+        assert_eq!(writer.split().0, b"Jello World! Good Bye!");
+        let cp = writer.checkpoint();
+        writer.clear();
+        assert!(writer.is_empty());
+        writer.rollback(cp);
+        assert!(writer.is_empty());
+        writer.write(b"\xFF").unwrap();
+        assert!(writer.as_str().is_err());
+        let (buf, len) = writer.into_inner();
+        assert_eq!(&buf[..len], b"\xFF");
+    }
+
+    #[test]
+    fn test_array_writer_split() {
+        let mut writer = ArrayWriter::<8>::default();
+        assert_eq!(writer, ArrayWriter::new());
+        writer.write(b"Head").unwrap();
+        let (head, mut tail) = writer.split();
+        assert_eq!(head, b"Head");
+        head[0] = b'h';
+        tail.write(b"Tail").unwrap();
+        assert_eq!(tail.write_byte(b'!'), Err(SerError::BufferFull));
+        assert_eq!(&*writer, b"head");
+        assert_eq!(writer.clone(), writer);
+    }
+}
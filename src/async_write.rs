@@ -0,0 +1,467 @@
+//! Asynchronous writer trait and adapters.
+use core::fmt;
+
+use super::*;
+
+/// Asynchronous counterpart of [`SerWrite`].
+///
+/// Serializers are synchronous, so they can't write directly to the
+/// implementations of this trait. Instead, a serialized message is streamed
+/// in chunks with [`write_async_with`].
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+#[allow(async_fn_in_trait)]
+pub trait AsyncSerWrite {
+    /// An error type returned from the trait methods.
+    type Error;
+    /// Write **all** bytes from `buf` to the sink.
+    ///
+    /// The returned future should be pending while the sink can't accept more data.
+    async fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error>;
+    /// Flush the sink ensuring that all written data reaches its destination.
+    #[inline]
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[allow(async_fn_in_trait)]
+impl<T: AsyncSerWrite + ?Sized> AsyncSerWrite for &'_ mut T {
+    type Error = T::Error;
+
+    #[inline(always)]
+    async fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        (**self).write(buf).await
+    }
+
+    #[inline(always)]
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        (**self).flush().await
+    }
+}
+
+/// An error returned from [`write_async_with`].
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AsyncSerError<S, E> {
+    /// An error serializing a value
+    Serialize(S),
+    /// An error from the asynchronous writer
+    Writer(E),
+}
+
+impl<S: fmt::Display, E: fmt::Display> fmt::Display for AsyncSerError<S, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsyncSerError::Serialize(err) => err.fmt(f),
+            AsyncSerError::Writer(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<S, E> core::error::Error for AsyncSerError<S, E>
+    where S: fmt::Display + fmt::Debug, E: fmt::Display + fmt::Debug
+{}
+
+/// Serialize a message with a `serialize` function and stream it to an
+/// asynchronous `writer` in chunks of up to `buf.len()` bytes.
+///
+/// The message is serialized once for each chunk to a [`WindowWriter`] capturing
+/// only the next chunk of the output, so `buf` doesn't need to hold the whole
+/// message. This trades CPU time for memory: a message of `n` bytes is serialized
+/// `n / buf.len()` times (rounded up), which makes the total cost quadratic when
+/// `buf` is small relative to the message. The `serialize` function must produce
+/// the same output each time it's called.
+///
+/// If the message fits in `buf`, it's serialized only once. To serialize many
+/// messages once each with a fixed staging buffer, use [`AsyncBufWriter`].
+///
+/// The first pass always serializes the whole message, so if `serialize`
+/// fails, nothing is written to the `writer`.
+///
+/// The returned future is pending whenever the `writer` applies backpressure.
+///
+/// # Panics
+/// Panics if `buf` is empty.
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub async fn write_async_with<W, F, S>(
+        mut writer: W,
+        buf: &mut [u8],
+        mut serialize: F
+    ) -> Result<(), AsyncSerError<S, W::Error>>
+    where W: AsyncSerWrite,
+          F: FnMut(&mut WindowWriter<'_>) -> Result<(), S>
+{
+    write_windows(&mut writer, buf, 0, &mut serialize).await?;
+    writer.flush().await.map_err(AsyncSerError::Writer)
+}
+
+/// Stream the output of `serialize` from the `offset` in windows of `buf.len()` bytes.
+async fn write_windows<W, F, S>(
+        writer: &mut W,
+        buf: &mut [u8],
+        mut offset: usize,
+        serialize: &mut F
+    ) -> Result<(), AsyncSerError<S, W::Error>>
+    where W: AsyncSerWrite,
+          F: FnMut(&mut WindowWriter<'_>) -> Result<(), S>
+{
+    loop {
+        let mut window = WindowWriter::new(&mut *buf, offset);
+        serialize(&mut window).map_err(AsyncSerError::Serialize)?;
+        let Window { data, total_len, .. } = window.finish();
+        if !data.is_empty() {
+            writer.write(data).await.map_err(AsyncSerError::Writer)?;
+        }
+        offset += data.len();
+        if offset >= total_len {
+            break Ok(())
+        }
+    }
+}
+
+/// A writer staging serialized messages in a buffer of `N` bytes and writing
+/// them to an asynchronous writer when the buffer runs out of room.
+///
+/// Each message passed to [`AsyncBufWriter::write_with`] is serialized once,
+/// directly into the free room of the buffer. If it doesn't fit, the buffered
+/// data is written to the inner writer and the message is serialized again into
+/// the empty buffer. Only a message larger than `N` bytes is streamed in windows
+/// like with [`write_async_with`], so choose `N` to fit the typical message.
+///
+/// Call [`AsyncBufWriter::flush`] or [`AsyncBufWriter::finish`] to write the buffered data.
+/// Data still in the buffer is discarded when the writer is dropped.
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+#[derive(Debug, Clone)]
+pub struct AsyncBufWriter<W, const N: usize> {
+    inner: W,
+    buf: [u8; N],
+    len: usize
+}
+
+impl<W, const N: usize> AsyncBufWriter<W, N> {
+    /// Create a new instance with an empty staging buffer.
+    pub fn new(inner: W) -> Self {
+        const { assert!(N > 0, "the staging buffer must not be empty") };
+        AsyncBufWriter { inner, buf: [0; N], len: 0 }
+    }
+    /// Return the number of bytes waiting in the staging buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Return whether the staging buffer is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Return the capacity of the staging buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+    /// Provide read-only access to the data waiting in the staging buffer.
+    #[inline]
+    pub fn buffered(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+    /// Provide read-only access to the inner writer.
+    #[inline]
+    pub fn writer_ref(&self) -> &W {
+        &self.inner
+    }
+}
+
+impl<W: AsyncSerWrite, const N: usize> AsyncBufWriter<W, N> {
+    /// Serialize a message with a `serialize` function into the staging buffer.
+    ///
+    /// The returned future is pending whenever the buffered data has to be written
+    /// and the inner writer applies backpressure.
+    ///
+    /// If `serialize` fails, nothing of the message is written or buffered.
+    pub async fn write_with<F, S>(&mut self, mut serialize: F) -> Result<(), AsyncSerError<S, W::Error>>
+        where F: FnMut(&mut WindowWriter<'_>) -> Result<(), S>
+    {
+        loop {
+            if self.len == N {
+                self.write_buffered().await.map_err(AsyncSerError::Writer)?;
+            }
+            let mut window = WindowWriter::new(&mut self.buf[self.len..], 0);
+            serialize(&mut window).map_err(AsyncSerError::Serialize)?;
+            let Window { data, total_len, .. } = window.finish();
+            if total_len == data.len() {
+                self.len += total_len;
+                return Ok(())
+            }
+            if self.len == 0 {
+                break
+            }
+            self.write_buffered().await.map_err(AsyncSerError::Writer)?;
+        }
+        // the message doesn't fit in the whole buffer
+        self.inner.write(&self.buf).await.map_err(AsyncSerError::Writer)?;
+        write_windows(&mut self.inner, &mut self.buf, N, &mut serialize).await
+    }
+    /// Write the buffered data and flush the inner writer.
+    pub async fn flush(&mut self) -> Result<(), W::Error> {
+        self.write_buffered().await?;
+        self.inner.flush().await
+    }
+    /// Flush the remaining data and return the inner writer.
+    pub async fn finish(mut self) -> Result<W, W::Error> {
+        self.flush().await?;
+        Ok(self.inner)
+    }
+    async fn write_buffered(&mut self) -> Result<(), W::Error> {
+        if self.len != 0 {
+            self.inner.write(&self.buf[..self.len]).await?;
+            self.len = 0;
+        }
+        Ok(())
+    }
+}
+
+/// An adapter implementing [`AsyncSerWrite`] for [`embedded_io_async::Write`] sinks.
+#[cfg(feature = "embedded-io-async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded-io-async")))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmbeddedIoAsyncWriter<W>(pub W);
+
+#[cfg(feature = "embedded-io-async")]
+impl<W> EmbeddedIoAsyncWriter<W> {
+    /// Create a new instance
+    #[inline(always)]
+    pub fn new(inner: W) -> Self {
+        EmbeddedIoAsyncWriter(inner)
+    }
+    /// Destruct self returning the inner sink.
+    #[inline(always)]
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+}
+
+#[cfg(feature = "embedded-io-async")]
+#[allow(async_fn_in_trait)]
+impl<W: embedded_io_async::Write> AsyncSerWrite for EmbeddedIoAsyncWriter<W> {
+    type Error = W::Error;
+
+    #[inline]
+    async fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.0.write_all(buf).await
+    }
+
+    #[inline]
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.0.flush().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::future::Future;
+    use core::pin::pin;
+    use core::task::{Context, Poll, Waker};
+    use super::*;
+
+    /// Poll a future to completion with a no-op waker and return the number
+    /// of times it was pending along with its output.
+    fn block_on<F: Future>(fut: F) -> (usize, F::Output) {
+        let mut fut = pin!(fut);
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut pending = 0;
+        loop {
+            match fut.as_mut().poll(&mut cx) {
+                Poll::Ready(res) => break (pending, res),
+                Poll::Pending => pending += 1
+            }
+        }
+    }
+
+    /// Yield once before completing.
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+        fn poll(mut self: core::pin::Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            }
+            else {
+                self.0 = true;
+                Poll::Pending
+            }
+        }
+    }
+
+    /// A sink accepting up to `chunk` bytes at a time, applying backpressure
+    /// before each chunk.
+    struct SlowSink<'a> {
+        out: SliceWriter<'a>,
+        chunk: usize,
+        flushed: bool
+    }
+
+    impl AsyncSerWrite for SlowSink<'_> {
+        type Error = SerError;
+
+        async fn write(&mut self, mut buf: &[u8]) -> SerResult<()> {
+            while !buf.is_empty() {
+                YieldNow(false).await;
+                let (head, tail) = buf.split_at(self.chunk.min(buf.len()));
+                self.out.write(head)?;
+                buf = tail;
+            }
+            Ok(())
+        }
+
+        async fn flush(&mut self) -> SerResult<()> {
+            self.flushed = true;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_write_async_with() {
+        let mut out = [0u8;22];
+        let mut sink = SlowSink { out: SliceWriter::new(&mut out[..12]), chunk: 5, flushed: false };
+        let mut buf = [0u8;16];
+        let (pending, res) = block_on(write_async_with(&mut sink, &mut buf, |w| {
+            w.write(b"Hello World!")
+        }));
+        assert_eq!(res, Ok(()));
+        assert_eq!(pending, 3);
+        assert!(sink.flushed);
+        assert_eq!(sink.out.as_ref(), b"Hello World!");
+        sink.out.clear();
+        sink.flushed = false;
+        let mut passes = 0;
+        let (pending, res) = block_on(write_async_with(&mut sink, &mut buf[..4], |w| {
+            passes += 1;
+            w.write(b"Hello ")?;
+            w.write_str("World!")
+        }));
+        assert_eq!(res, Ok(()));
+        assert_eq!(passes, 3);
+        assert_eq!(pending, 3);
+        assert!(sink.flushed);
+        assert_eq!(sink.out.as_ref(), b"Hello World!");
+        sink.out.clear();
+        sink.flushed = false;
+        let mut passes = 0;
+        let (pending, res) = block_on(write_async_with(&mut sink, &mut buf, |_| {
+            passes += 1;
+            Ok::<_, SerError>(())
+        }));
+        assert_eq!(res, Ok(()));
+        assert_eq!((passes, pending), (1, 0));
+        assert!(sink.flushed);
+        assert!(sink.out.is_empty());
+        sink.flushed = false;
+        let (_, res) = block_on(write_async_with(&mut sink, &mut buf[..4], |w| {
+            w.write(b"Hello World!")?;
+            Err(SerError::InvalidFrame)
+        }));
+        assert_eq!(res, Err(AsyncSerError::Serialize(SerError::InvalidFrame)));
+        assert!(!sink.flushed);
+        assert!(sink.out.is_empty());
+        let (_, res) = block_on(write_async_with(&mut sink, &mut buf, |w| {
+            w.write(b"Hello World! Bye")
+        }));
+        assert_eq!(res, Err(AsyncSerError::Writer(SerError::BufferFull)));
+        #[cfg(feature = "std")]
+        {
+            assert_eq!(std::format!("{}", res.unwrap_err()), "buffer is full");
+        }
+        let mut sink = SlowSink { out: SliceWriter::new(&mut out), chunk: 5, flushed: false };
+        let message = b"Hello World! Good Bye!";
+        for len in 1..=message.len() + 1 {
+            sink.out.clear();
+            let (_, res) = block_on(write_async_with(&mut sink, &mut buf[..len.min(16)], |w| {
+                w.write(message)
+            }));
+            assert_eq!(res, Ok(()));
+            assert_eq!(sink.out.as_ref(), message);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the window buffer must not be empty")]
+    fn test_write_async_with_empty_buf() {
+        let mut out = [0u8;4];
+        let mut sink = SlowSink { out: SliceWriter::new(&mut out), chunk: 5, flushed: false };
+        let _ = block_on(write_async_with(&mut sink, &mut [], |w| w.write(b"Hi")));
+    }
+
+    #[test]
+    fn test_async_buf_writer() {
+        let mut out = [0u8;64];
+        let sink = SlowSink { out: SliceWriter::new(&mut out), chunk: 16, flushed: false };
+        let mut writer = AsyncBufWriter::<_, 8>::new(sink);
+        assert_eq!(writer.capacity(), 8);
+        let mut passes = 0;
+        let (pending, res) = block_on(writer.write_with(|w| {
+            passes += 1;
+            w.write(b"Hello")
+        }));
+        assert_eq!(res, Ok(()));
+        assert_eq!((passes, pending), (1, 0));
+        assert_eq!(writer.buffered(), b"Hello");
+        let mut passes = 0;
+        let (pending, res) = block_on(writer.write_with(|w| {
+            passes += 1;
+            w.write(b" World")
+        }));
+        assert_eq!(res, Ok(()));
+        assert_eq!((passes, pending), (2, 1));
+        assert_eq!(writer.buffered(), b" World");
+        assert_eq!(writer.writer_ref().out.as_ref(), b"Hello");
+        let mut passes = 0;
+        let (_, res) = block_on(writer.write_with(|w| {
+            passes += 1;
+            w.write(b"!!")
+        }));
+        assert_eq!(res, Ok(()));
+        assert_eq!(passes, 1);
+        assert_eq!(writer.len(), 8);
+        let (_, res) = block_on(writer.write_with(|w| {
+            w.write(b"??")?;
+            Err(SerError::InvalidFrame)
+        }));
+        assert_eq!(res, Err(AsyncSerError::Serialize(SerError::InvalidFrame)));
+        assert!(writer.is_empty());
+        assert_eq!(writer.writer_ref().out.as_ref(), b"Hello World!!");
+        let mut passes = 0;
+        let (_, res) = block_on(writer.write_with(|w| {
+            passes += 1;
+            w.write(b" Good Bye! Good Bye!")
+        }));
+        assert_eq!(res, Ok(()));
+        assert_eq!(passes, 3);
+        assert!(writer.is_empty());
+        block_on(writer.write_with(|w| w.write(b"."))).1.unwrap();
+        assert!(!writer.writer_ref().flushed);
+        let sink = block_on(writer.finish()).1.unwrap();
+        assert!(sink.flushed);
+        assert_eq!(sink.out.as_ref(), b"Hello World!! Good Bye! Good Bye!.");
+    }
+
+    #[cfg(feature = "embedded-io-async")]
+    #[test]
+    fn test_embedded_io_async_writer() {
+        let mut out = [0u8;12];
+        let mut writer = EmbeddedIoAsyncWriter::new(&mut out[..]);
+        let mut buf = [0u8;5];
+        let (pending, res) = block_on(write_async_with(&mut writer, &mut buf, |w| {
+            w.write(b"Hello World!")
+        }));
+        assert_eq!(res, Ok(()));
+        assert_eq!(pending, 0);
+        assert_eq!(writer.into_inner(), &[]);
+        assert_eq!(&out, b"Hello World!");
+        let mut writer = EmbeddedIoAsyncWriter::new(&mut out[..4]);
+        let (_, res) = block_on(write_async_with(&mut writer, &mut buf, |w| {
+            w.write(b"Hello World!")
+        }));
+        assert!(matches!(res, Err(AsyncSerError::Writer(_))));
+    }
+}
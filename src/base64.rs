@@ -0,0 +1,195 @@
+//! Base-64 encoder.
+//!
+//! A matching in-place decoder is provided by the `ser-write-json` crate.
+use super::*;
+
+static ALPHABET: &[u8;64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode an array of bytes as Base-64 ASCII armour codes into a [`SerWrite`] implementing object.
+///
+/// This function does not append Base-64 `'='` padding characters by itself
+/// and instead returns the number of padding characters required: 0-2.
+pub fn encode<W: SerWrite>(ser: &mut W, bytes: &[u8]) -> Result<u8, W::Error> {
+    let mut chunks = bytes.chunks_exact(3);
+    for slice in chunks.by_ref() {
+        let [a,b,c] = slice.try_into().unwrap();
+        let output = [
+            a >> 2,
+            ((a & 0x03) << 4) | ((b & 0xF0) >> 4),
+            ((b & 0x0F) << 2) | ((c & 0xC0) >> 6),
+            c & 0x3F
+        ].map(|n| ALPHABET[(n & 0x3F) as usize]);
+        ser.write(&output)?;
+    }
+    match chunks.remainder() {
+        [a, b] => {
+            let output = [
+                a >> 2,
+                ((a & 0x03) << 4) | ((b & 0xF0) >> 4),
+                ((b & 0x0F) << 2)
+            ].map(|n| ALPHABET[(n & 0x3F) as usize]);
+            ser.write(&output)?;
+            Ok(1)
+        }
+        [a] => {
+            let output = [
+                a >> 2,
+                ((a & 0x03) << 4),
+            ].map(|n| ALPHABET[(n & 0x3F) as usize]);
+            ser.write(&output)?;
+            Ok(2)
+        }
+        _ => Ok(0)
+    }
+}
+
+/// A writer encoding data as Base-64 ASCII armour codes on the fly into the inner writer.
+///
+/// Data may be written in chunks of any size, the 1-2 bytes left over from a chunk
+/// are carried over to the next one.
+///
+/// Call [`Base64Writer::finish`] to encode the leftover bytes and write the `'='` padding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Base64Writer<W> {
+    inner: W,
+    tail: [u8;3],
+    tail_len: u8
+}
+
+impl<W> Base64Writer<W> {
+    /// Create a new instance
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        Base64Writer { inner, tail: [0;3], tail_len: 0 }
+    }
+    /// Provide read-only access to the inner writer.
+    #[inline]
+    pub fn writer_ref(&self) -> &W {
+        &self.inner
+    }
+}
+
+impl<W: SerWrite> Base64Writer<W> {
+    /// Encode the leftover bytes followed by the `'='` padding and return the inner writer.
+    pub fn finish(mut self) -> Result<W, W::Error> {
+        let padding = encode(&mut self.inner, &self.tail[..self.tail_len as usize])?;
+        self.inner.write(&b"=="[..padding as usize])?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: SerWrite> SerWrite for Base64Writer<W> {
+    type Error = W::Error;
+
+    fn write(&mut self, mut buf: &[u8]) -> Result<(), Self::Error> {
+        let tail_len = self.tail_len as usize;
+        if tail_len != 0 {
+            let take = (3 - tail_len).min(buf.len());
+            let (head, rest) = buf.split_at(take);
+            self.tail[tail_len..tail_len + take].copy_from_slice(head);
+            self.tail_len += take as u8;
+            if self.tail_len < 3 {
+                return Ok(())
+            }
+            encode(&mut self.inner, &self.tail)?;
+            self.tail_len = 0;
+            buf = rest;
+        }
+        let (full, rest) = buf.split_at(buf.len() - buf.len() % 3);
+        encode(&mut self.inner, full)?;
+        self.tail[..rest.len()].copy_from_slice(rest);
+        self.tail_len = rest.len() as u8;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_encode() {
+        let mut buf = [0u8;6];
+        let writer = &mut SliceWriter::new(&mut buf);
+        encode(writer, &[]).unwrap();
+        assert_eq!(writer.as_ref(), b"");
+        encode(writer, &[0]).unwrap();
+        assert_eq!(writer.as_ref(), b"AA");
+        writer.clear();
+        encode(writer, &[1]).unwrap();
+        assert_eq!(writer.as_ref(), b"AQ");
+        writer.clear();
+        encode(writer, &[0,0]).unwrap();
+        assert_eq!(writer.as_ref(), b"AAA");
+        writer.clear();
+        encode(writer, &[0,0,0]).unwrap();
+        assert_eq!(writer.as_ref(), b"AAAA");
+        writer.clear();
+        encode(writer, &[0,0,0,0]).unwrap();
+        assert_eq!(writer.as_ref(), b"AAAAAA");
+        writer.clear();
+        encode(writer, &[1,2]).unwrap();
+        assert_eq!(writer.as_ref(), b"AQI");
+        writer.clear();
+        encode(writer, &[1,2,3]).unwrap();
+        assert_eq!(writer.as_ref(), b"AQID");
+        writer.clear();
+        encode(writer, &[1,2,3,4]).unwrap();
+        assert_eq!(writer.as_ref(), b"AQIDBA");
+        writer.clear();
+        encode(writer, &[0x80]).unwrap();
+        assert_eq!(writer.as_ref(), b"gA");
+        writer.clear();
+        encode(writer, &[0x80,0x81]).unwrap();
+        assert_eq!(writer.as_ref(), b"gIE");
+        writer.clear();
+        encode(writer, &[0x80,0x81,0x82]).unwrap();
+        assert_eq!(writer.as_ref(), b"gIGC");
+        writer.clear();
+        encode(writer, &[0xFF]).unwrap();
+        assert_eq!(writer.as_ref(), b"/w");
+        writer.clear();
+        encode(writer, &[0xFF,0xFF]).unwrap();
+        assert_eq!(writer.as_ref(), b"//8");
+        writer.clear();
+        encode(writer, &[0xFF,0xFF,0xFE]).unwrap();
+        assert_eq!(writer.as_ref(), b"///+");
+        writer.clear();
+        encode(writer, &[0xFF,0xFF,0xFF]).unwrap();
+        assert_eq!(writer.as_ref(), b"////");
+        assert_eq!(encode(writer, b"12345"), Err(SerError::BufferFull));
+        let mut buf = [0u8;1];
+        let writer = &mut SliceWriter::new(&mut buf);
+        assert_eq!(encode(writer, b"1"), Err(SerError::BufferFull));
+        let mut buf = [0u8;1];
+        let writer = &mut SliceWriter::new(&mut buf);
+        assert_eq!(encode(writer, b"12"), Err(SerError::BufferFull));
+    }
+
+    #[test]
+    fn test_base64_writer() {
+        let mut buf = [0u8;32];
+        for chunk_size in 1..=7 {
+            let mut writer = Base64Writer::new(SliceWriter::new(&mut buf));
+            for chunk in b"Hello World!!".chunks(chunk_size) {
+                writer.write(chunk).unwrap();
+            }
+            writer.write(b"").unwrap();
+            assert_eq!(writer.writer_ref().as_ref(), b"SGVsbG8gV29ybGQh");
+            assert_eq!(writer.finish().unwrap().as_ref(), b"SGVsbG8gV29ybGQhIQ==");
+        }
+        let mut writer = Base64Writer::new(SliceWriter::new(&mut buf));
+        writer.write_byte(0xFF).unwrap();
+        writer.write_str("ab").unwrap();
+        writer.write_byte(0x01).unwrap();
+        assert_eq!(writer.writer_ref().as_ref(), b"/2Fi");
+        writer.write(b"\x02").unwrap();
+        assert_eq!(writer.finish().unwrap().as_ref(), b"/2FiAQI=");
+        let writer = Base64Writer::new(SliceWriter::new(&mut buf));
+        assert_eq!(writer.finish().unwrap().as_ref(), b"");
+        let mut buf = [0u8;6];
+        let mut writer = Base64Writer::new(SliceWriter::new(&mut buf));
+        writer.write(b"12345").unwrap();
+        assert_eq!(writer.finish(), Err(SerError::BufferFull));
+    }
+}
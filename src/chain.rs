@@ -0,0 +1,133 @@
+//! A writer scattering data over a chain of buffers.
+use super::*;
+
+/// A writer filling a chain of disjoint slice segments one after another.
+///
+/// Like [`SliceWriter`], a write either succeeds entirely or returns
+/// [`SerError::BufferFull`] without writing anything.
+#[derive(Debug, PartialEq)]
+pub struct ChainWriter<'a, 'b> {
+    segments: &'a mut [&'b mut [u8]],
+    index: usize,
+    fill: usize,
+    len: usize,
+    capacity: usize
+}
+
+impl<'a, 'b> ChainWriter<'a, 'b> {
+    /// Create a new instance
+    pub fn new(segments: &'a mut [&'b mut [u8]]) -> Self {
+        let capacity = segments.iter().map(|seg| seg.len()).sum();
+        ChainWriter { segments, index: 0, fill: 0, len: 0, capacity }
+    }
+    /// Return the total number of bytes written.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Return whether nothing has been written.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Return the total capacity of all segments.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+    /// Return the remaining free capacity.
+    #[inline]
+    pub fn rem_capacity(&self) -> usize {
+        self.capacity - self.len
+    }
+    /// Return the number of segments up to and including the one containing
+    /// the last written byte.
+    ///
+    /// The last of them is the only one which may be partially filled.
+    pub fn segments_used(&self) -> usize {
+        if self.len == 0 { 0 } else { self.index + 1 }
+    }
+    /// Return the number of bytes written to the last of the used segments.
+    #[inline]
+    pub fn last_segment_len(&self) -> usize {
+        self.fill
+    }
+    /// Provide read-only access to the segments.
+    #[inline]
+    pub fn segments(&self) -> &[&'b mut [u8]] {
+        self.segments
+    }
+    /// Reset the cursor to the beginning of the first segment.
+    pub fn clear(&mut self) {
+        self.index = 0;
+        self.fill = 0;
+        self.len = 0;
+    }
+}
+
+impl SerWrite for ChainWriter<'_, '_> {
+    type Error = SerError;
+
+    fn write(&mut self, mut buf: &[u8]) -> SerResult<()> {
+        if buf.len() > self.rem_capacity() {
+            return Err(SerError::BufferFull)
+        }
+        self.len += buf.len();
+        while !buf.is_empty() {
+            let segment = &mut self.segments[self.index][self.fill..];
+            if segment.is_empty() {
+                self.index += 1;
+                self.fill = 0;
+                continue
+            }
+            let (head, tail) = buf.split_at(segment.len().min(buf.len()));
+            segment[..head.len()].copy_from_slice(head);
+            self.fill += head.len();
+            buf = tail;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_writer() {
+        let mut a = [0u8;4];
+        let mut b = [0u8;0];
+        let mut c = [0u8;6];
+        let mut d = [0u8;4];
+        let mut segments: [&mut [u8]; 4] = [&mut a, &mut b, &mut c, &mut d];
+        let mut writer = ChainWriter::new(&mut segments);
+        assert_eq!(writer.capacity(), 14);
+        assert!(writer.is_empty());
+        assert_eq!(writer.segments_used(), 0);
+        assert_eq!(writer.last_segment_len(), 0);
+        writer.write(b"Hell").unwrap();
+        assert_eq!(writer.segments_used(), 1);
+        assert_eq!(writer.last_segment_len(), 4);
+        writer.write_byte(b'o').unwrap();
+        assert_eq!(writer.segments_used(), 3);
+        assert_eq!(writer.last_segment_len(), 1);
+        writer.write_str(" World!").unwrap();
+        assert_eq!(writer.len(), 12);
+        assert_eq!(writer.rem_capacity(), 2);
+        assert_eq!(writer.segments_used(), 4);
+        assert_eq!(writer.last_segment_len(), 2);
+        assert_eq!(writer.write(b"!!!"), Err(SerError::BufferFull));
+        assert_eq!(writer.len(), 12);
+        writer.write(b"!!").unwrap();
+        assert_eq!(writer.rem_capacity(), 0);
+        assert_eq!(writer.write_byte(b'!'), Err(SerError::BufferFull));
+        assert_eq!(&*writer.segments()[3], b"d!!!");
+        writer.clear();
+        writer.write(b"Bye").unwrap();
+        assert_eq!(writer.segments_used(), 1);
+        assert_eq!(writer.last_segment_len(), 3);
+        assert_eq!(&a, b"Byel");
+        assert_eq!(&c, b"o Worl");
+        assert_eq!(&d, b"d!!!");
+    }
+}
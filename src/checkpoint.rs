@@ -0,0 +1,189 @@
+//! Checkpoint and rollback support for writers.
+use super::*;
+
+/// Writers implementing this trait can discard data written after a marked position.
+///
+/// Use [`Transaction`] to have the data rolled back automatically unless committed.
+pub trait SerCheckpoint: SerWrite {
+    /// Return a checkpoint marking the current position of the writer.
+    fn checkpoint(&self) -> usize;
+    /// Discard all data written after the `checkpoint`.
+    ///
+    /// The `checkpoint` should have been returned from [`SerCheckpoint::checkpoint`]
+    /// and no data written before it should have been removed since then.
+    fn rollback(&mut self, checkpoint: usize);
+}
+
+impl<T: SerCheckpoint> SerCheckpoint for &'_ mut T {
+    #[inline(always)]
+    fn checkpoint(&self) -> usize {
+        (**self).checkpoint()
+    }
+
+    #[inline(always)]
+    fn rollback(&mut self, checkpoint: usize) {
+        (**self).rollback(checkpoint)
+    }
+}
+
+impl SerCheckpoint for SliceWriter<'_> {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.len = self.len.min(checkpoint);
+    }
+}
+
+/// A transactional writer wrapping a [`SerCheckpoint`] implementation.
+///
+/// All data written via a `Transaction` is rolled back when the `Transaction` is
+/// dropped, unless [`Transaction::commit`] is called.
+pub struct Transaction<'a, W: SerCheckpoint + ?Sized> {
+    writer: &'a mut W,
+    checkpoint: usize,
+    committed: bool
+}
+
+impl<'a, W: SerCheckpoint + ?Sized> Transaction<'a, W> {
+    /// Create a new `Transaction` marking the current position of the `writer`.
+    #[inline]
+    pub fn new(writer: &'a mut W) -> Self {
+        let checkpoint = writer.checkpoint();
+        Transaction { writer, checkpoint, committed: false }
+    }
+    /// Return the checkpoint marked when the `Transaction` was created.
+    #[inline]
+    pub fn checkpoint(&self) -> usize {
+        self.checkpoint
+    }
+    /// Keep the data written so far.
+    #[inline]
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+    /// Discard the data written so far.
+    ///
+    /// This is what happens when the `Transaction` is dropped without committing.
+    #[inline]
+    pub fn rollback(self) {}
+}
+
+impl<W: SerCheckpoint + ?Sized> Drop for Transaction<'_, W> {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.writer.rollback(self.checkpoint);
+        }
+    }
+}
+
+impl<W: SerCheckpoint + ?Sized> SerWrite for Transaction<'_, W> {
+    type Error = W::Error;
+
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.writer.write(buf)
+    }
+
+    #[inline(always)]
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::Error> {
+        self.writer.write_byte(byte)
+    }
+
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.writer.write_str(s)
+    }
+
+    #[inline(always)]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        self.writer.write_vectored(bufs)
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) -> Result<(), Self::Error> {
+        self.writer.reserve(additional)
+    }
+}
+
+/// Call `f` with a transactional writer and commit the written data only if `f` succeeds.
+///
+/// On error, the `writer` is left exactly as it was before the call.
+pub fn write_atomic<W, F, T, E>(writer: &mut W, f: F) -> Result<T, E>
+    where W: SerCheckpoint + ?Sized,
+          F: FnOnce(&mut Transaction<'_, W>) -> Result<T, E>
+{
+    let mut tx = Transaction::new(writer);
+    let res = f(&mut tx)?;
+    tx.commit();
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slice_writer_checkpoint() {
+        let mut buf = [0u8;16];
+        let mut writer = SliceWriter::new(&mut buf);
+        writer.write(b"Hello").unwrap();
+        let cp = writer.checkpoint();
+        assert_eq!(cp, 5);
+        writer.write(b" World!").unwrap();
+        writer.rollback(cp);
+        assert_eq!(writer.as_ref(), b"Hello");
+        writer.rollback(10);
+        assert_eq!(writer.as_ref(), b"Hello");
+    }
+
+    #[test]
+    fn test_transaction() {
+        let mut buf = [0u8;16];
+        let mut writer = SliceWriter::new(&mut buf);
+        writer.write(b"Hello").unwrap();
+        let mut tx = Transaction::new(&mut writer);
+        assert_eq!(tx.checkpoint(), 5);
+        tx.write(b" World").unwrap();
+        tx.write_byte(b'!').unwrap();
+        tx.commit();
+        assert_eq!(writer.as_ref(), b"Hello World!");
+        let mut tx = Transaction::new(&mut writer);
+        tx.write_str(" Bye").unwrap();
+        tx.rollback();
+        assert_eq!(writer.as_ref(), b"Hello World!");
+        {
+            let mut tx = Transaction::new(&mut writer);
+            tx.write_str(" Bye").unwrap();
+        }
+        assert_eq!(writer.as_ref(), b"Hello World!");
+    }
+
+    #[test]
+    fn test_transaction_forwarding() {
+        let mut buf = [0u8;16];
+        let mut writer = crate::test_util::CallRecorder::new(&mut buf);
+        let mut tx = Transaction::new(&mut writer);
+        tx.reserve(11).unwrap();
+        tx.write_vectored(&[b"Hello", b" ", b"World"]).unwrap();
+        tx.commit();
+        assert_eq!((writer.vectored, writer.reserved), (1, 11));
+        assert_eq!(writer.writer.as_ref(), b"Hello World");
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let mut buf = [0u8;16];
+        let mut writer = SliceWriter::new(&mut buf);
+        assert_eq!(write_atomic(&mut writer, |w| w.write(b"Hello World!")), Ok(()));
+        assert_eq!(write_atomic(&mut writer, |w| {
+            w.write(b" Good")?;
+            w.write(b" Bye!")
+        }), Err(SerError::BufferFull));
+        assert_eq!(writer.as_ref(), b"Hello World!");
+        assert_eq!(write_atomic(&mut writer, |w| w.write(b" Bye").map(|_| 4)), Ok(4));
+        assert_eq!(writer.as_ref(), b"Hello World! Bye");
+    }
+}
@@ -0,0 +1,452 @@
+//! Checksums computed on the fly while writing.
+//!
+//! CRC implementations use 256-entry lookup tables generated at compile time.
+use super::*;
+
+/// A checksum algorithm which can be updated incrementally.
+pub trait Checksum {
+    /// The number of bytes of the checksum value.
+    const WIDTH: usize;
+    /// Update the checksum with `data`.
+    fn update(&mut self, data: &[u8]);
+    /// Return the checksum of all data passed to [`Checksum::update`] so far.
+    fn value(&self) -> u32;
+    /// Reset the checksum to its initial state.
+    fn reset(&mut self);
+}
+
+/// The byte order in which the checksum value is appended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ByteOrder {
+    /// Least significant byte first
+    LittleEndian,
+    /// Most significant byte first
+    BigEndian
+}
+
+const fn crc8_table(poly: u8) -> [u8; 256] {
+    let mut table = [0u8; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ poly } else { crc << 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const fn crc16_table(poly: u16) -> [u16; 256] {
+    let mut table = [0u16; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ poly } else { crc << 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const fn crc32_reflected_table(poly: u32) -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ poly } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+static CRC8_TABLE: [u8; 256] = crc8_table(0x07);
+static CRC16_CCITT_TABLE: [u16; 256] = crc16_table(0x1021);
+static CRC32_TABLE: [u32; 256] = crc32_reflected_table(0xEDB8_8320);
+
+/// CRC-8/SMBUS: polynomial `0x07`, initial value `0x00`, no reflection, no final XOR.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Crc8(u8);
+
+impl Crc8 {
+    /// Create a new instance
+    #[inline]
+    pub const fn new() -> Self {
+        Crc8(0)
+    }
+}
+
+impl Checksum for Crc8 {
+    const WIDTH: usize = 1;
+
+    fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.0 = CRC8_TABLE[(self.0 ^ byte) as usize];
+        }
+    }
+    #[inline]
+    fn value(&self) -> u32 {
+        self.0 as u32
+    }
+    #[inline]
+    fn reset(&mut self) {
+        self.0 = 0;
+    }
+}
+
+/// CRC-16/CCITT-FALSE (CRC-16/IBM-3740): polynomial `0x1021`, initial value `0xFFFF`,
+/// no reflection, no final XOR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Crc16Ccitt(u16);
+
+impl Crc16Ccitt {
+    /// Create a new instance
+    #[inline]
+    pub const fn new() -> Self {
+        Crc16Ccitt(0xFFFF)
+    }
+}
+
+impl Default for Crc16Ccitt {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Crc16Ccitt {
+    const WIDTH: usize = 2;
+
+    fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            let index = ((self.0 >> 8) as u8 ^ byte) as usize;
+            self.0 = (self.0 << 8) ^ CRC16_CCITT_TABLE[index];
+        }
+    }
+    #[inline]
+    fn value(&self) -> u32 {
+        self.0 as u32
+    }
+    #[inline]
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// CRC-32/ISO-HDLC (the CRC-32 of Ethernet, zlib and PNG): reflected polynomial
+/// `0xEDB88320`, initial value and final XOR `0xFFFFFFFF`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Crc32(u32);
+
+impl Crc32 {
+    /// Create a new instance
+    #[inline]
+    pub const fn new() -> Self {
+        Crc32(!0)
+    }
+}
+
+impl Default for Crc32 {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Crc32 {
+    const WIDTH: usize = 4;
+
+    fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            let index = (self.0 as u8 ^ byte) as usize;
+            self.0 = (self.0 >> 8) ^ CRC32_TABLE[index];
+        }
+    }
+    #[inline]
+    fn value(&self) -> u32 {
+        !self.0
+    }
+    #[inline]
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Fletcher-16 checksum with both sums modulo 255.
+///
+/// The value has the second sum in the high byte.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Fletcher16 {
+    sum1: u16,
+    sum2: u16
+}
+
+impl Fletcher16 {
+    /// Create a new instance
+    #[inline]
+    pub const fn new() -> Self {
+        Fletcher16 { sum1: 0, sum2: 0 }
+    }
+}
+
+impl Checksum for Fletcher16 {
+    const WIDTH: usize = 2;
+
+    fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.sum1 = (self.sum1 + byte as u16) % 255;
+            self.sum2 = (self.sum2 + self.sum1) % 255;
+        }
+    }
+    #[inline]
+    fn value(&self) -> u32 {
+        ((self.sum2 as u32) << 8) | self.sum1 as u32
+    }
+    #[inline]
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// Adler-32 checksum as used by zlib.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Adler32 {
+    a: u32,
+    b: u32
+}
+
+impl Adler32 {
+    const MOD: u32 = 65521;
+    /// The maximum number of bytes which can be summed before the sums may overflow.
+    const NMAX: usize = 5552;
+    /// Create a new instance
+    #[inline]
+    pub const fn new() -> Self {
+        Adler32 { a: 1, b: 0 }
+    }
+}
+
+impl Default for Adler32 {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Adler32 {
+    const WIDTH: usize = 4;
+
+    fn update(&mut self, data: &[u8]) {
+        for chunk in data.chunks(Self::NMAX) {
+            for &byte in chunk {
+                self.a += byte as u32;
+                self.b += self.a;
+            }
+            self.a %= Self::MOD;
+            self.b %= Self::MOD;
+        }
+    }
+    #[inline]
+    fn value(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+    #[inline]
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// A writer updating a [`Checksum`] with all bytes successfully written to the inner writer.
+///
+/// Call [`ChecksumWriter::finish`] to append the checksum value to the inner writer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChecksumWriter<W, C> {
+    inner: W,
+    checksum: C
+}
+
+impl<W, C: Checksum + Default> ChecksumWriter<W, C> {
+    /// Create a new instance with the checksum in its initial state.
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        ChecksumWriter { inner, checksum: C::default() }
+    }
+}
+
+impl<W, C: Checksum> ChecksumWriter<W, C> {
+    /// Create a new instance with the provided `checksum`.
+    #[inline]
+    pub fn with_checksum(inner: W, checksum: C) -> Self {
+        ChecksumWriter { inner, checksum }
+    }
+    /// Return the checksum of the data written so far.
+    #[inline]
+    pub fn value(&self) -> u32 {
+        self.checksum.value()
+    }
+    /// Provide read-only access to the checksum.
+    #[inline]
+    pub fn checksum(&self) -> &C {
+        &self.checksum
+    }
+    /// Reset the checksum to its initial state.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.checksum.reset()
+    }
+    /// Provide access to the inner writer.
+    ///
+    /// Data written directly to the inner writer doesn't update the checksum.
+    #[inline]
+    pub fn writer(&mut self) -> &mut W {
+        &mut self.inner
+    }
+    /// Provide read-only access to the inner writer.
+    #[inline]
+    pub fn writer_ref(&self) -> &W {
+        &self.inner
+    }
+    /// Destruct self returning the inner writer and the checksum.
+    #[inline]
+    pub fn into_parts(self) -> (W, C) {
+        (self.inner, self.checksum)
+    }
+}
+
+impl<W: SerWrite, C: Checksum> ChecksumWriter<W, C> {
+    /// Append the [`Checksum::WIDTH`] bytes of the checksum value in the given byte `order`
+    /// and return the inner writer.
+    pub fn finish(mut self, order: ByteOrder) -> Result<W, W::Error> {
+        let value = self.checksum.value();
+        match order {
+            ByteOrder::LittleEndian => {
+                self.inner.write(&value.to_le_bytes()[..C::WIDTH])?
+            }
+            ByteOrder::BigEndian => {
+                self.inner.write(&value.to_be_bytes()[4 - C::WIDTH..])?
+            }
+        }
+        Ok(self.inner)
+    }
+}
+
+impl<W: SerWrite, C: Checksum> SerWrite for ChecksumWriter<W, C> {
+    type Error = W::Error;
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.inner.write(buf)?;
+        self.checksum.update(buf);
+        Ok(())
+    }
+    #[inline]
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::Error> {
+        self.inner.write_byte(byte)?;
+        self.checksum.update(&[byte]);
+        Ok(())
+    }
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.inner.write_str(s)?;
+        self.checksum.update(s.as_bytes());
+        Ok(())
+    }
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        self.inner.write_vectored(bufs)?;
+        for buf in bufs {
+            self.checksum.update(buf);
+        }
+        Ok(())
+    }
+    #[inline]
+    fn reserve(&mut self, additional: usize) -> Result<(), Self::Error> {
+        self.inner.reserve(additional)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check<C: Checksum + Default>(data: &[u8]) -> u32 {
+        let mut checksum = C::default();
+        checksum.update(data);
+        let value = checksum.value();
+        checksum.reset();
+        for chunk in data.chunks(3) {
+            checksum.update(chunk);
+        }
+        assert_eq!(checksum.value(), value);
+        value
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(check::<Crc8>(b"123456789"), 0xF4);
+        assert_eq!(check::<Crc16Ccitt>(b"123456789"), 0x29B1);
+        assert_eq!(check::<Crc32>(b"123456789"), 0xCBF4_3926);
+        assert_eq!(check::<Fletcher16>(b"abcde"), 0xC8F0);
+        assert_eq!(check::<Fletcher16>(b"abcdefgh"), 0x0627);
+        assert_eq!(check::<Adler32>(b"Wikipedia"), 0x11E6_0398);
+        assert_eq!(check::<Crc8>(b""), 0);
+        assert_eq!(check::<Crc16Ccitt>(b""), 0xFFFF);
+        assert_eq!(check::<Crc32>(b""), 0);
+        assert_eq!(check::<Adler32>(b""), 1);
+        assert_eq!(check::<Adler32>(&[0xFF; 6000]), 0xA497_59EA);
+    }
+
+    #[test]
+    fn test_checksum_writer() {
+        let mut buf = [0u8;16];
+        let mut writer = ChecksumWriter::<_, Crc32>::new(SliceWriter::new(&mut buf));
+        writer.write(b"1234").unwrap();
+        writer.write_byte(b'5').unwrap();
+        writer.write_str("6789").unwrap();
+        assert_eq!(writer.value(), 0xCBF4_3926);
+        assert_eq!(writer.writer_ref().as_ref(), b"123456789");
+        let writer = writer.finish(ByteOrder::LittleEndian).unwrap();
+        assert_eq!(writer.as_ref(), b"123456789\x26\x39\xF4\xCB");
+
+        let mut writer = ChecksumWriter::<_, Crc32>::new(SliceWriter::new(&mut buf));
+        writer.write(b"123456789").unwrap();
+        let writer = writer.finish(ByteOrder::BigEndian).unwrap();
+        assert_eq!(writer.as_ref(), b"123456789\xCB\xF4\x39\x26");
+
+        let mut writer = ChecksumWriter::with_checksum(SliceWriter::new(&mut buf), Crc16Ccitt::new());
+        writer.write(b"123456789").unwrap();
+        assert_eq!(writer.finish(ByteOrder::BigEndian).unwrap().as_ref(), b"123456789\x29\xB1");
+        let mut writer = ChecksumWriter::<_, Crc8>::new(SliceWriter::new(&mut buf));
+        writer.write(b"123456789").unwrap();
+        assert_eq!(writer.finish(ByteOrder::LittleEndian).unwrap().as_ref(), b"123456789\xF4");
+
+        let mut writer = ChecksumWriter::<_, Fletcher16>::new(SliceWriter::new(&mut buf));
+        writer.write(b"1234567890abcdef").unwrap();
+        assert_eq!(writer.write_byte(b'g'), Err(SerError::BufferFull));
+        assert_eq!(writer.value(), check::<Fletcher16>(b"1234567890abcdef"));
+        writer.reset();
+        assert_eq!(*writer.checksum(), Fletcher16::new());
+        writer.writer().clear();
+        assert_eq!(writer.finish(ByteOrder::LittleEndian).unwrap().as_ref(), b"\x00\x00");
+
+        let mut buf = [0u8;16];
+        let mut writer = ChecksumWriter::<_, Crc32>::new(crate::test_util::CallRecorder::new(&mut buf));
+        writer.reserve(9).unwrap();
+        writer.write_vectored(&[b"1234", b"", b"56789"]).unwrap();
+        assert_eq!(writer.value(), 0xCBF4_3926);
+        assert_eq!((writer.writer_ref().vectored, writer.writer_ref().reserved), (1, 9));
+        assert_eq!(writer.writer_ref().writer.as_ref(), b"123456789");
+    }
+}
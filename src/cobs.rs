@@ -0,0 +1,197 @@
+//! Consistent Overhead Byte Stuffing (COBS) framing.
+//!
+//! Encoded frames contain no zero bytes and are terminated with a single `0x00` delimiter.
+use super::*;
+
+/// Maximum number of data bytes in a single COBS block.
+const MAX_BLOCK: usize = 254;
+
+/// A writer encoding data with COBS on the fly into the inner writer.
+///
+/// Data is buffered in blocks of up to 254 bytes, so the inner writer receives
+/// whole blocks preceded by their code bytes.
+///
+/// Call [`CobsWriter::finish`] to write the last block and the terminating delimiter.
+#[derive(Debug, Clone)]
+pub struct CobsWriter<W> {
+    inner: W,
+    block: [u8; MAX_BLOCK],
+    len: u8,
+    after_full: bool
+}
+
+impl<W> CobsWriter<W> {
+    /// Create a new instance starting a new frame.
+    pub fn new(inner: W) -> Self {
+        CobsWriter { inner, block: [0; MAX_BLOCK], len: 0, after_full: false }
+    }
+    /// Provide read-only access to the inner writer.
+    #[inline]
+    pub fn writer_ref(&self) -> &W {
+        &self.inner
+    }
+}
+
+impl<W: SerWrite> CobsWriter<W> {
+    /// Write the buffered block to the inner writer.
+    ///
+    /// The block is discarded even if writing fails, so the writer remains usable,
+    /// although the frame being encoded is then incomplete.
+    fn write_block(&mut self) -> Result<(), W::Error> {
+        let len = core::mem::take(&mut self.len);
+        self.inner.write_byte(len + 1)?;
+        self.inner.write(&self.block[..len as usize])
+    }
+    /// Write the last block followed by a `0x00` delimiter and return the inner writer.
+    pub fn finish(mut self) -> Result<W, W::Error> {
+        if self.len != 0 || !self.after_full {
+            self.write_block()?;
+        }
+        self.inner.write_byte(0)?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: SerWrite> SerWrite for CobsWriter<W> {
+    type Error = W::Error;
+
+    fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        for &byte in buf {
+            self.write_byte(byte)?;
+        }
+        Ok(())
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::Error> {
+        if byte == 0 {
+            self.after_full = false;
+            return self.write_block()
+        }
+        self.block[self.len as usize] = byte;
+        self.len += 1;
+        if self.len as usize == MAX_BLOCK {
+            self.write_block()?;
+            self.after_full = true;
+        }
+        else {
+            self.after_full = false;
+        }
+        Ok(())
+    }
+}
+
+/// Decode a COBS encoded frame in-place until the first `0x00` delimiter or
+/// until the end of the slice.
+///
+/// Return the decoded portion of the slice.
+///
+/// Return [`SerError::InvalidFrame`] if a block is truncated.
+pub fn decode_in_place(buf: &mut [u8]) -> SerResult<&mut [u8]> {
+    let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    let mut rd = 0;
+    let mut wr = 0;
+    while rd < end {
+        let code = buf[rd] as usize;
+        rd += 1;
+        let block_end = rd + code - 1;
+        if block_end > end {
+            return Err(SerError::InvalidFrame)
+        }
+        buf.copy_within(rd..block_end, wr);
+        wr += code - 1;
+        rd = block_end;
+        if code != MAX_BLOCK + 1 && rd < end {
+            buf[wr] = 0;
+            wr += 1;
+        }
+    }
+    Ok(&mut buf[..wr])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode<'a>(out: &'a mut [u8], data: &[u8]) -> &'a mut [u8] {
+        let mut writer = CobsWriter::new(SliceWriter::new(out));
+        writer.write(data).unwrap();
+        writer.finish().unwrap().split().0
+    }
+
+    #[test]
+    fn test_cobs() {
+        let mut out = [0u8;320];
+        let mut data = [0u8;300];
+        for (input, expected) in [
+            (&b""[..], &b"\x01\x00"[..]),
+            (b"\x00", b"\x01\x01\x00"),
+            (b"\x00\x00", b"\x01\x01\x01\x00"),
+            (b"\x00\x11\x00", b"\x01\x02\x11\x01\x00"),
+            (b"\x11\x22\x00\x33", b"\x03\x11\x22\x02\x33\x00"),
+            (b"\x11\x22\x33\x44", b"\x05\x11\x22\x33\x44\x00"),
+            (b"\x11\x00\x00\x00", b"\x02\x11\x01\x01\x01\x00"),
+        ] {
+            let encoded = encode(&mut out, input);
+            assert_eq!(encoded, expected);
+            assert_eq!(decode_in_place(encoded).unwrap(), input);
+        }
+        for (i, byte) in data.iter_mut().enumerate() {
+            *byte = (i % 255) as u8 + 1;
+        }
+        let encoded = encode(&mut out, &data[..254]);
+        assert_eq!(encoded.len(), 256);
+        assert_eq!(encoded[0], 0xFF);
+        assert_eq!(&encoded[1..255], &data[..254]);
+        assert_eq!(encoded[255], 0);
+        assert_eq!(decode_in_place(encoded).unwrap(), &data[..254]);
+
+        let encoded = encode(&mut out, &data[..255]);
+        assert_eq!(encoded.len(), 258);
+        assert_eq!(&encoded[255..], b"\x02\xFF\x00");
+        assert_eq!(decode_in_place(encoded).unwrap(), &data[..255]);
+
+        data[254] = 0;
+        let encoded = encode(&mut out, &data[..255]);
+        assert_eq!(&encoded[255..], b"\x01\x01\x00");
+        assert_eq!(decode_in_place(encoded).unwrap(), &data[..255]);
+
+        data[100] = 0;
+        let encoded = encode(&mut out, &data[..300]);
+        assert!(!encoded[..encoded.len() - 1].contains(&0));
+        assert_eq!(decode_in_place(encoded).unwrap(), &data[..300]);
+    }
+
+    #[test]
+    fn test_cobs_decode() {
+        let mut frame = *b"\x03\x11\x22\x02\x33\x00\x02\x44\x00";
+        assert_eq!(decode_in_place(&mut frame).unwrap(), b"\x11\x22\x00\x33");
+        assert_eq!(decode_in_place(&mut frame[6..]).unwrap(), b"\x44");
+        assert_eq!(decode_in_place(&mut []).unwrap(), b"");
+        assert_eq!(decode_in_place(&mut [0]).unwrap(), b"");
+        assert_eq!(decode_in_place(&mut [3, 1, 0]), Err(SerError::InvalidFrame));
+        assert_eq!(decode_in_place(&mut [3, 1]), Err(SerError::InvalidFrame));
+    }
+
+    #[test]
+    fn test_cobs_writer_error() {
+        let mut out = [0u8;4];
+        let mut writer = CobsWriter::new(SliceWriter::new(&mut out));
+        writer.write(b"\x11\x22\x33").unwrap();
+        assert_eq!(writer.writer_ref().len(), 0);
+        writer.write_byte(0).unwrap();
+        assert_eq!(writer.writer_ref().len(), 4);
+        assert_eq!(writer.write_byte(0), Err(SerError::BufferFull));
+        assert_eq!(writer.finish().unwrap_err(), SerError::BufferFull);
+        // a failed full block must not leave the writer in an invalid state
+        let mut out = [0u8;100];
+        let mut writer = CobsWriter::new(SliceWriter::new(&mut out));
+        assert_eq!(writer.write(&[0x55;254]), Err(SerError::BufferFull));
+        assert_eq!(writer.write(b"\x11\x22"), Ok(()));
+        assert_eq!(writer.write_byte(0x33), Ok(()));
+        assert_eq!(writer.write_byte(0), Ok(()));
+        assert_eq!(writer.writer_ref().as_ref(), b"\xFF\x04\x11\x22\x33");
+        assert_eq!(writer.write(&[0x55;254]), Err(SerError::BufferFull));
+        assert_eq!(writer.write_byte(0x44), Ok(()));
+        assert_eq!(writer.finish().unwrap().as_ref(), b"\xFF\x04\x11\x22\x33\xFF\x02\x44\x00");
+    }
+}
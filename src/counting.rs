@@ -0,0 +1,143 @@
+//! Writers for measuring the size of the serialized output.
+use super::*;
+
+/// A writer discarding all data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NullWriter;
+
+impl SerWrite for NullWriter {
+    type Error = SerError;
+
+    #[inline(always)]
+    fn write(&mut self, _buf: &[u8]) -> SerResult<()> {
+        Ok(())
+    }
+    #[inline(always)]
+    fn write_byte(&mut self, _byte: u8) -> SerResult<()> {
+        Ok(())
+    }
+}
+
+/// A writer counting bytes successfully written to the inner writer.
+///
+/// By default it wraps a [`NullWriter`], so it just counts the bytes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CountingWriter<W = NullWriter> {
+    inner: W,
+    count: usize
+}
+
+impl CountingWriter {
+    /// Create a new instance only counting bytes.
+    #[inline]
+    pub fn new() -> Self {
+        CountingWriter { inner: NullWriter, count: 0 }
+    }
+}
+
+impl<W> CountingWriter<W> {
+    /// Create a new instance counting bytes written to the `inner` writer.
+    #[inline]
+    pub fn with_writer(inner: W) -> Self {
+        CountingWriter { inner, count: 0 }
+    }
+    /// Return the number of bytes written so far.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+    /// Reset the counter to zero.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.count = 0;
+    }
+    /// Provide access to the inner writer.
+    #[inline]
+    pub fn writer(&mut self) -> &mut W {
+        &mut self.inner
+    }
+    /// Provide read-only access to the inner writer.
+    #[inline]
+    pub fn writer_ref(&self) -> &W {
+        &self.inner
+    }
+    /// Destruct self returning the inner writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: SerWrite> SerWrite for CountingWriter<W> {
+    type Error = W::Error;
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.inner.write(buf)?;
+        self.count += buf.len();
+        Ok(())
+    }
+    #[inline]
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::Error> {
+        self.inner.write_byte(byte)?;
+        self.count += 1;
+        Ok(())
+    }
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.inner.write_str(s)?;
+        self.count += s.len();
+        Ok(())
+    }
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        self.inner.write_vectored(bufs)?;
+        self.count += bufs.iter().map(|buf| buf.len()).sum::<usize>();
+        Ok(())
+    }
+    #[inline]
+    fn reserve(&mut self, additional: usize) -> Result<(), Self::Error> {
+        self.inner.reserve(additional)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_null_writer() {
+        let mut writer = NullWriter;
+        writer.write(b"Hello World!").unwrap();
+        writer.write_byte(b' ').unwrap();
+        writer.write_str("Good Bye!").unwrap();
+    }
+
+    #[test]
+    fn test_counting_writer() {
+        let mut writer = CountingWriter::new();
+        assert_eq!(writer.count(), 0);
+        writer.write(b"Hello World!").unwrap();
+        writer.write_byte(b' ').unwrap();
+        writer.write_str("Good Bye!").unwrap();
+        assert_eq!(writer.count(), 22);
+        writer.reserve(10).unwrap();
+        writer.write_vectored(&[b" ", b"", b"Hi!"]).unwrap();
+        assert_eq!(writer.count(), 26);
+        writer.reset();
+        assert_eq!(writer.count(), 0);
+        assert_eq!(writer.into_inner(), NullWriter);
+
+        let mut buf = [0u8;13];
+        let mut writer = CountingWriter::with_writer(SliceWriter::new(&mut buf));
+        writer.write(b"Hello World!").unwrap();
+        writer.write_byte(b' ').unwrap();
+        assert_eq!(writer.write_str("Good Bye!"), Err(SerError::BufferFull));
+        assert_eq!(writer.write(b"!"), Err(SerError::BufferFull));
+        assert_eq!(writer.write_byte(b'!'), Err(SerError::BufferFull));
+        assert_eq!(writer.count(), 13);
+        assert_eq!(writer.writer_ref().len(), 13);
+        writer.writer().clear();
+        assert_eq!(writer.into_inner().as_ref(), b"");
+    }
+}
@@ -0,0 +1,208 @@
+//! An object-safe writer trait with a unified error type.
+use super::*;
+
+/// An object-safe counterpart of [`SerWrite`] with [`SerError`] as the unified error type.
+///
+/// Serializers writing to `&mut dyn DynSerWrite` are compiled once for all writers,
+/// which reduces the code size when serializing to many different writer types.
+///
+/// It's implemented for all writers with `SerError` as their error type.
+/// Wrap other writers in [`DynWriter`].
+pub trait DynSerWrite {
+    /// Write **all** bytes from `buf` to the internal buffer.
+    fn dyn_write(&mut self, buf: &[u8]) -> SerResult<()>;
+    /// Write a single `byte` to the internal buffer.
+    fn dyn_write_byte(&mut self, byte: u8) -> SerResult<()>;
+    /// Write a **whole** string to the internal buffer.
+    fn dyn_write_str(&mut self, s: &str) -> SerResult<()>;
+    /// Write **all** bytes from each of the `bufs` to the internal buffer, in order.
+    fn dyn_write_vectored(&mut self, bufs: &[&[u8]]) -> SerResult<()>;
+    /// Hint that at least `additional` more bytes are about to be written.
+    fn dyn_reserve(&mut self, additional: usize) -> SerResult<()>;
+}
+
+impl<W: SerWrite<Error = SerError>> DynSerWrite for W {
+    #[inline]
+    fn dyn_write(&mut self, buf: &[u8]) -> SerResult<()> {
+        self.write(buf)
+    }
+    #[inline]
+    fn dyn_write_byte(&mut self, byte: u8) -> SerResult<()> {
+        self.write_byte(byte)
+    }
+    #[inline]
+    fn dyn_write_str(&mut self, s: &str) -> SerResult<()> {
+        self.write_str(s)
+    }
+    #[inline]
+    fn dyn_write_vectored(&mut self, bufs: &[&[u8]]) -> SerResult<()> {
+        self.write_vectored(bufs)
+    }
+    #[inline]
+    fn dyn_reserve(&mut self, additional: usize) -> SerResult<()> {
+        self.reserve(additional)
+    }
+}
+
+impl SerWrite for dyn DynSerWrite + '_ {
+    type Error = SerError;
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> SerResult<()> {
+        self.dyn_write(buf)
+    }
+    #[inline]
+    fn write_byte(&mut self, byte: u8) -> SerResult<()> {
+        self.dyn_write_byte(byte)
+    }
+    #[inline]
+    fn write_str(&mut self, s: &str) -> SerResult<()> {
+        self.dyn_write_str(s)
+    }
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> SerResult<()> {
+        self.dyn_write_vectored(bufs)
+    }
+    #[inline]
+    fn reserve(&mut self, additional: usize) -> SerResult<()> {
+        self.dyn_reserve(additional)
+    }
+}
+
+/// An adapter implementing [`DynSerWrite`] for writers with any error type.
+///
+/// An error from the inner writer is stored and [`SerError::WriterFailed`] is returned
+/// instead. Retrieve the original error with [`DynWriter::take_error`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynWriter<W: SerWrite> {
+    inner: W,
+    error: Option<W::Error>
+}
+
+impl<W: SerWrite> DynWriter<W> {
+    /// Create a new instance
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        DynWriter { inner, error: None }
+    }
+    /// Take the last error returned by the inner writer.
+    #[inline]
+    pub fn take_error(&mut self) -> Option<W::Error> {
+        self.error.take()
+    }
+    /// Provide access to the inner writer.
+    #[inline]
+    pub fn writer(&mut self) -> &mut W {
+        &mut self.inner
+    }
+    /// Provide read-only access to the inner writer.
+    #[inline]
+    pub fn writer_ref(&self) -> &W {
+        &self.inner
+    }
+    /// Destruct self returning the inner writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+    fn store<T>(&mut self, res: Result<T, W::Error>) -> SerResult<T> {
+        res.map_err(|err| {
+            self.error = Some(err);
+            SerError::WriterFailed
+        })
+    }
+}
+
+impl<W: SerWrite> DynSerWrite for DynWriter<W> {
+    fn dyn_write(&mut self, buf: &[u8]) -> SerResult<()> {
+        let res = self.inner.write(buf);
+        self.store(res)
+    }
+    fn dyn_write_byte(&mut self, byte: u8) -> SerResult<()> {
+        let res = self.inner.write_byte(byte);
+        self.store(res)
+    }
+    fn dyn_write_str(&mut self, s: &str) -> SerResult<()> {
+        let res = self.inner.write_str(s);
+        self.store(res)
+    }
+    fn dyn_write_vectored(&mut self, bufs: &[&[u8]]) -> SerResult<()> {
+        let res = self.inner.write_vectored(bufs);
+        self.store(res)
+    }
+    fn dyn_reserve(&mut self, additional: usize) -> SerResult<()> {
+        let res = self.inner.reserve(additional);
+        self.store(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Failing(usize);
+
+    impl SerWrite for Failing {
+        type Error = usize;
+
+        fn write(&mut self, buf: &[u8]) -> Result<(), usize> {
+            self.0 += buf.len();
+            Err(self.0)
+        }
+    }
+
+    fn write_dyn(writer: &mut dyn DynSerWrite) -> SerResult<()> {
+        writer.write(b"Hello")?;
+        writer.write_byte(b' ')?;
+        writer.write_str("World!")
+    }
+
+    #[test]
+    fn test_dyn_ser_write() {
+        let mut buf = [0u8;12];
+        let mut writer = SliceWriter::new(&mut buf);
+        write_dyn(&mut writer).unwrap();
+        assert_eq!(writer.as_ref(), b"Hello World!");
+        assert_eq!(write_dyn(&mut writer), Err(SerError::BufferFull));
+
+        let mut writer = DynWriter::new(SliceWriter::new(&mut buf));
+        write_dyn(&mut writer).unwrap();
+        assert_eq!(writer.take_error(), None);
+        assert_eq!(writer.writer_ref().as_ref(), b"Hello World!");
+        writer.writer().clear();
+        assert!(writer.into_inner().is_empty());
+
+        let mut writer = DynWriter::new(Failing(0));
+        assert_eq!(write_dyn(&mut writer), Err(SerError::WriterFailed));
+        assert_eq!(writer.take_error(), Some(5));
+        assert_eq!(writer.take_error(), None);
+        assert_eq!(writer.dyn_write_byte(0), Err(SerError::WriterFailed));
+        assert_eq!(writer.take_error(), Some(6));
+        assert_eq!(writer.dyn_write_str("!"), Err(SerError::WriterFailed));
+        assert_eq!(writer.take_error(), Some(7));
+        assert_eq!(writer.dyn_write_vectored(&[b"!", b"!"]), Err(SerError::WriterFailed));
+        assert_eq!(writer.take_error(), Some(8));
+        assert_eq!(writer.dyn_reserve(10), Ok(()));
+    }
+
+    #[test]
+    fn test_dyn_ser_write_forwarding() {
+        fn write_vectored_dyn(writer: &mut dyn DynSerWrite) -> SerResult<()> {
+            writer.reserve(12)?;
+            writer.write_vectored(&[b"Hello", b" ", b"World!"])
+        }
+        let mut buf = [0u8;12];
+        let mut writer = crate::test_util::CallRecorder::new(&mut buf);
+        write_vectored_dyn(&mut writer).unwrap();
+        assert_eq!((writer.vectored, writer.reserved), (1, 12));
+        assert_eq!(writer.writer.as_ref(), b"Hello World!");
+
+        let mut writer = DynWriter::new(crate::test_util::CallRecorder::new(&mut buf));
+        write_vectored_dyn(&mut writer).unwrap();
+        assert_eq!((writer.writer_ref().vectored, writer.writer_ref().reserved), (1, 12));
+        assert_eq!(writer.writer_ref().writer.as_ref(), b"Hello World!");
+        assert_eq!(write_vectored_dyn(&mut writer), Err(SerError::WriterFailed));
+        assert_eq!(writer.take_error(), Some(SerError::BufferFull));
+    }
+}
@@ -0,0 +1,193 @@
+//! A writer flushing data in chunks from a small staging buffer.
+use super::*;
+
+/// A writer staging data in a buffer of `N` bytes and passing it to a `flush`
+/// function each time the buffer is full.
+///
+/// The `flush` function is called with chunks of exactly `N` bytes, except for
+/// the last chunk passed by [`FlushingWriter::flush`] or [`FlushingWriter::finish`].
+/// To forward chunks to an inner sink, use a closure like `|chunk| sink.write(chunk)`.
+///
+/// Errors returned by the `flush` function are passed through as the writer's errors.
+/// New bytes are staged only after a successful flush, so when a write fails, none of
+/// its data remains in the buffer and the same write can be retried once the sink
+/// recovers. However, whole chunks of a write longer than the free room in the buffer
+/// may have been flushed before the error, in which case a retry duplicates them.
+///
+/// Data still in the buffer is discarded when the writer is dropped.
+#[derive(Debug, Clone)]
+pub struct FlushingWriter<F, const N: usize> {
+    flush: F,
+    buf: [u8; N],
+    len: usize
+}
+
+impl<F, const N: usize> FlushingWriter<F, N> {
+    /// Create a new instance with an empty staging buffer.
+    pub fn new(flush: F) -> Self {
+        const { assert!(N > 0, "the staging buffer must not be empty") };
+        FlushingWriter { flush, buf: [0; N], len: 0 }
+    }
+    /// Return the number of bytes waiting in the staging buffer.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    /// Return whether the staging buffer is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Return the capacity of the staging buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        N
+    }
+    /// Provide read-only access to the data waiting in the staging buffer.
+    #[inline]
+    pub fn buffered(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl<F, E, const N: usize> FlushingWriter<F, N>
+    where F: FnMut(&[u8]) -> Result<(), E>
+{
+    /// Pass the data waiting in the staging buffer to the `flush` function, if any.
+    pub fn flush(&mut self) -> Result<(), E> {
+        if self.len != 0 {
+            (self.flush)(&self.buf[..self.len])?;
+            self.len = 0;
+        }
+        Ok(())
+    }
+    /// Flush the remaining data and return the `flush` function.
+    pub fn finish(mut self) -> Result<F, E> {
+        self.flush()?;
+        Ok(self.flush)
+    }
+}
+
+impl<F, E, const N: usize> SerWrite for FlushingWriter<F, N>
+    where F: FnMut(&[u8]) -> Result<(), E>
+{
+    type Error = E;
+
+    fn write(&mut self, mut buf: &[u8]) -> Result<(), Self::Error> {
+        let room = N - self.len;
+        if buf.len() < room {
+            self.buf[self.len..self.len + buf.len()].copy_from_slice(buf);
+            self.len += buf.len();
+            return Ok(())
+        }
+        if self.len != 0 {
+            let (head, tail) = buf.split_at(room);
+            self.buf[self.len..].copy_from_slice(head);
+            (self.flush)(&self.buf)?;
+            self.len = 0;
+            buf = tail;
+        }
+        let mut chunks = buf.chunks_exact(N);
+        for chunk in chunks.by_ref() {
+            (self.flush)(chunk)?;
+        }
+        let rem = chunks.remainder();
+        self.buf[..rem.len()].copy_from_slice(rem);
+        self.len = rem.len();
+        Ok(())
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::Error> {
+        self.buf[self.len] = byte;
+        if self.len + 1 == N {
+            (self.flush)(&self.buf)?;
+            self.len = 0;
+        }
+        else {
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flushing_writer() {
+        let mut out = [0u8;32];
+        let mut sink = SliceWriter::new(&mut out);
+        let mut chunks = 0;
+        let mut writer = FlushingWriter::<_, 4>::new(|chunk: &[u8]| {
+            assert!(chunks == 6 || chunk.len() == 4);
+            chunks += 1;
+            sink.write(chunk)
+        });
+        assert_eq!(writer.capacity(), 4);
+        writer.write(b"He").unwrap();
+        assert_eq!(writer.len(), 2);
+        assert_eq!(writer.buffered(), b"He");
+        writer.write(b"ll").unwrap();
+        assert!(writer.is_empty());
+        writer.write_byte(b'o').unwrap();
+        writer.write(b" World!").unwrap();
+        assert_eq!(writer.buffered(), b"");
+        writer.write_str(" Good Bye!").unwrap();
+        assert_eq!(writer.buffered(), b"e!");
+        writer.write_byte(b'!').unwrap();
+        assert_eq!(writer.buffered(), b"e!!");
+        writer.write(b"..").unwrap();
+        assert_eq!(writer.buffered(), b".");
+        let _ = writer.finish().unwrap();
+        assert_eq!(chunks, 7);
+        assert_eq!(sink.as_ref(), b"Hello World! Good Bye!!..");
+    }
+
+    #[test]
+    fn test_flushing_writer_error() {
+        let mut out = [0u8;10];
+        let mut sink = SliceWriter::new(&mut out);
+        let mut writer = FlushingWriter::<_, 8>::new(|chunk: &[u8]| sink.write(chunk));
+        writer.write(b"Hello World!").unwrap();
+        assert_eq!(writer.buffered(), b"rld!");
+        assert_eq!(writer.flush(), Err(SerError::BufferFull));
+        assert_eq!(writer.buffered(), b"rld!");
+        writer.write(b"123").unwrap();
+        assert_eq!(writer.write_byte(b'4'), Err(SerError::BufferFull));
+        assert_eq!(writer.buffered(), b"rld!123");
+        assert_eq!(writer.write(b"45"), Err(SerError::BufferFull));
+        assert_eq!(writer.buffered(), b"rld!123");
+        assert_eq!(writer.finish().err(), Some(SerError::BufferFull));
+        assert_eq!(sink.as_ref(), b"Hello Wo");
+    }
+
+    #[test]
+    fn test_flushing_writer_retry() {
+        use core::cell::Cell;
+        let mut out = [0u8;16];
+        let mut sink = SliceWriter::new(&mut out);
+        let failures = Cell::new(0);
+        let mut writer = FlushingWriter::<_, 4>::new(|chunk: &[u8]| {
+            if failures.get() != 0 {
+                failures.set(failures.get() - 1);
+                return Err(SerError::BufferFull)
+            }
+            sink.write(chunk)
+        });
+        writer.write(b"Hel").unwrap();
+        failures.set(1);
+        assert_eq!(writer.write(b"lo W"), Err(SerError::BufferFull));
+        assert_eq!(writer.buffered(), b"Hel");
+        writer.write(b"lo W").unwrap();
+        assert_eq!(writer.buffered(), b"o W");
+        failures.set(1);
+        assert_eq!(writer.write_byte(b'o'), Err(SerError::BufferFull));
+        assert_eq!(writer.buffered(), b"o W");
+        writer.write_byte(b'o').unwrap();
+        assert!(writer.is_empty());
+        writer.write_str("rld!").unwrap();
+        let _ = writer.finish().unwrap();
+        assert_eq!(sink.as_ref(), b"Hello World!");
+    }
+}
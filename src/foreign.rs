@@ -1,8 +1,8 @@
 /// Implementation for foreign types
 #[cfg(feature = "std")]
-use std::{vec::Vec, collections::VecDeque, io::Cursor};
+use std::{vec::Vec, string::String, collections::VecDeque, io::Cursor};
 #[cfg(all(feature = "alloc",not(feature = "std")))]
-use alloc::{vec::Vec, collections::VecDeque};
+use alloc::{vec::Vec, string::String, collections::VecDeque};
 
 #[allow(unused_imports)]
 use super::*;
@@ -13,6 +13,12 @@ use std::collections::TryReserveError;
 #[cfg(all(not(feature = "std"), feature = "alloc"))]
 use alloc::collections::TryReserveError;
 
+impl From<core::str::Utf8Error> for SerError {
+    fn from(_err: core::str::Utf8Error) -> SerError {
+        SerError::InvalidUtf8
+    }
+}
+
 #[cfg(any(feature = "std", feature = "alloc"))]
 impl From<TryReserveError> for SerError {
     fn from(_err: TryReserveError) -> SerError {
@@ -42,6 +48,17 @@ impl SerWrite for Vec<u8> {
         self.push(byte);
         Ok(())
     }
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> SerResult<()> {
+        self.try_reserve(bufs.iter().map(|buf| buf.len()).sum())?;
+        for buf in bufs {
+            self.extend_from_slice(buf);
+        }
+        Ok(())
+    }
+    #[inline]
+    fn reserve(&mut self, additional: usize) -> SerResult<()> {
+        Ok(self.try_reserve(additional)?)
+    }
 }
 
 #[cfg(any(feature = "std", feature = "alloc"))]
@@ -63,6 +80,41 @@ impl SerWrite for VecDeque<u8> {
     }
 }
 
+/// Only complete UTF-8 sequences are accepted, otherwise [`SerError::InvalidUtf8`] is returned.
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
+impl SerWrite for String {
+    type Error = SerError;
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> SerResult<()> {
+        self.write_str(core::str::from_utf8(buf)?)
+    }
+    #[inline]
+    fn write_str(&mut self, s: &str) -> SerResult<()> {
+        self.try_reserve(s.len())?;
+        self.push_str(s);
+        Ok(())
+    }
+    #[inline]
+    fn reserve(&mut self, additional: usize) -> SerResult<()> {
+        Ok(self.try_reserve(additional)?)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
+impl SerCheckpoint for String {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.truncate(checkpoint);
+    }
+}
+
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl<T> SerWrite for Cursor<T>
@@ -76,6 +128,92 @@ impl<T> SerWrite for Cursor<T>
     }
 }
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
+impl SerCheckpoint for Vec<u8> {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.truncate(checkpoint);
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
+impl SerCheckpoint for VecDeque<u8> {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.truncate(checkpoint);
+    }
+}
+
+impl SerRead for &[u8] {
+    type Error = SerError;
+
+    #[inline]
+    fn fill_buf(&mut self) -> SerResult<&[u8]> {
+        Ok(self)
+    }
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        *self = &self[amt..];
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "std", feature = "alloc"))))]
+impl SerRead for VecDeque<u8> {
+    type Error = SerError;
+
+    #[inline]
+    fn fill_buf(&mut self) -> SerResult<&[u8]> {
+        Ok(self.as_slices().0)
+    }
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.drain(..amt);
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<T: AsRef<[u8]>> SerRead for Cursor<T> {
+    type Error = SerError;
+
+    #[inline]
+    fn fill_buf(&mut self) -> SerResult<&[u8]> {
+        let buf = self.get_ref().as_ref();
+        let pos = usize::try_from(self.position()).map_or(buf.len(), |pos| pos.min(buf.len()));
+        Ok(&buf[pos..])
+    }
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.set_position(self.position() + amt as u64);
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<R: std::io::Read> SerRead for std::io::BufReader<R> {
+    type Error = std::io::Error;
+
+    #[inline]
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        std::io::BufRead::fill_buf(self)
+    }
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        std::io::BufRead::consume(self, amt)
+    }
+}
+
 #[cfg(feature = "arrayvec")]
 #[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
 impl<T> From<arrayvec::CapacityError<T>> for SerError {
@@ -98,6 +236,66 @@ impl<const CAP: usize> SerWrite for arrayvec::ArrayVec<u8, CAP> {
     }
 }
 
+/// Only complete UTF-8 sequences are accepted, otherwise [`SerError::InvalidUtf8`] is returned.
+#[cfg(feature = "arrayvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+impl<const CAP: usize> SerWrite for arrayvec::ArrayString<CAP> {
+    type Error = SerError;
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> SerResult<()> {
+        self.write_str(core::str::from_utf8(buf)?)
+    }
+    #[inline]
+    fn write_str(&mut self, s: &str) -> SerResult<()> {
+        self.try_push_str(s).map_err(|_| SerError::BufferFull)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+impl<const CAP: usize> SerCheckpoint for arrayvec::ArrayString<CAP> {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.truncate(checkpoint);
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+impl<const CAP: usize> SerCheckpoint for arrayvec::ArrayVec<u8, CAP> {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.truncate(checkpoint);
+    }
+}
+
+/// Consumed bytes are removed from the front of the vector by shifting the remaining
+/// ones, so reading a large buffer in small steps takes quadratic time.
+/// Prefer reading from a `&[u8]` slice of the buffer in that case.
+#[cfg(feature = "arrayvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "arrayvec")))]
+impl<const CAP: usize> SerRead for arrayvec::ArrayVec<u8, CAP> {
+    type Error = SerError;
+
+    #[inline]
+    fn fill_buf(&mut self) -> SerResult<&[u8]> {
+        Ok(self.as_slice())
+    }
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.drain(..amt);
+    }
+}
+
 #[cfg(feature = "heapless")]
 #[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
 impl<LenT: heapless::LenType, const CAP: usize> SerWrite for heapless::Vec<u8, CAP, LenT> {
@@ -126,6 +324,204 @@ impl<LenT: heapless::LenType> SerWrite for heapless::VecView<u8, LenT> {
     }
 }
 
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<const N: usize> SerWrite for heapless::Deque<u8, N> {
+    type Error = SerError;
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> SerResult<()> {
+        self.as_mut_view().write(buf)
+    }
+    #[inline]
+    fn write_byte(&mut self, byte: u8) -> SerResult<()> {
+        self.push_back(byte).map_err(|_| SerError::BufferFull)
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl SerWrite for heapless::deque::DequeView<u8> {
+    type Error = SerError;
+
+    fn write(&mut self, buf: &[u8]) -> SerResult<()> {
+        if self.storage_capacity() - self.storage_len() < buf.len() {
+            return Err(SerError::BufferFull)
+        }
+        for &byte in buf {
+            self.push_back(byte).map_err(|_| SerError::BufferFull)?;
+        }
+        Ok(())
+    }
+    #[inline]
+    fn write_byte(&mut self, byte: u8) -> SerResult<()> {
+        self.push_back(byte).map_err(|_| SerError::BufferFull)
+    }
+}
+
+/// Only complete UTF-8 sequences are accepted, otherwise [`SerError::InvalidUtf8`] is returned.
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<LenT: heapless::LenType, const CAP: usize> SerWrite for heapless::String<CAP, LenT> {
+    type Error = SerError;
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> SerResult<()> {
+        self.as_mut_view().write(buf)
+    }
+    #[inline]
+    fn write_str(&mut self, s: &str) -> SerResult<()> {
+        self.as_mut_view().write_str(s)
+    }
+}
+
+/// Only complete UTF-8 sequences are accepted, otherwise [`SerError::InvalidUtf8`] is returned.
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<LenT: heapless::LenType> SerWrite for heapless::string::StringView<LenT> {
+    type Error = SerError;
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> SerResult<()> {
+        self.write_str(core::str::from_utf8(buf)?)
+    }
+    #[inline]
+    fn write_str(&mut self, s: &str) -> SerResult<()> {
+        self.push_str(s).map_err(|_| SerError::BufferFull)
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<LenT: heapless::LenType, const CAP: usize> SerCheckpoint for heapless::String<CAP, LenT> {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.truncate(checkpoint);
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<LenT: heapless::LenType> SerCheckpoint for heapless::string::StringView<LenT> {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.truncate(checkpoint);
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<const N: usize> SerCheckpoint for heapless::Deque<u8, N> {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.as_mut_view().rollback(checkpoint)
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl SerCheckpoint for heapless::deque::DequeView<u8> {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.storage_len()
+    }
+    fn rollback(&mut self, checkpoint: usize) {
+        while self.storage_len() > checkpoint {
+            self.pop_back();
+        }
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<LenT: heapless::LenType, const CAP: usize> SerCheckpoint for heapless::Vec<u8, CAP, LenT> {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.truncate(checkpoint);
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<LenT: heapless::LenType> SerCheckpoint for heapless::VecView<u8, LenT> {
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.truncate(checkpoint);
+    }
+}
+
+/// Consumed bytes are removed from the front of the vector by shifting the remaining
+/// ones, so reading a large buffer in small steps takes quadratic time.
+/// Prefer reading from a `&[u8]` slice of the buffer in that case.
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<LenT: heapless::LenType, const CAP: usize> SerRead for heapless::Vec<u8, CAP, LenT> {
+    type Error = SerError;
+
+    #[inline]
+    fn fill_buf(&mut self) -> SerResult<&[u8]> {
+        Ok(self.as_slice())
+    }
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.drain(..amt);
+    }
+}
+
+/// As with `heapless::Vec`, each consume shifts the remaining bytes to the front,
+/// so prefer reading large buffers from a `&[u8]` slice.
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<LenT: heapless::LenType> SerRead for heapless::VecView<u8, LenT> {
+    type Error = SerError;
+
+    #[inline]
+    fn fill_buf(&mut self) -> SerResult<&[u8]> {
+        Ok(self.as_slice())
+    }
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        self.drain(..amt);
+    }
+}
+
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+impl<const N: usize> SerRead for heapless::Deque<u8, N> {
+    type Error = SerError;
+
+    #[inline]
+    fn fill_buf(&mut self) -> SerResult<&[u8]> {
+        Ok(self.as_slices().0)
+    }
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        for _ in 0..amt {
+            self.pop_front();
+        }
+    }
+}
+
 #[cfg(feature = "smallvec")]
 #[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
 impl From<smallvec::CollectionAllocErr> for SerError {
@@ -152,6 +548,32 @@ impl<const CAP: usize> SerWrite for smallvec::SmallVec<[u8; CAP]>
         self.push(byte);
         Ok(())
     }
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> SerResult<()> {
+        self.try_reserve(bufs.iter().map(|buf| buf.len()).sum())?;
+        for buf in bufs {
+            self.extend_from_slice(buf);
+        }
+        Ok(())
+    }
+    #[inline]
+    fn reserve(&mut self, additional: usize) -> SerResult<()> {
+        Ok(self.try_reserve(additional)?)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "smallvec")))]
+impl<const CAP: usize> SerCheckpoint for smallvec::SmallVec<[u8; CAP]>
+    where [u8; CAP]: smallvec::Array<Item=u8>,
+{
+    #[inline]
+    fn checkpoint(&self) -> usize {
+        self.len()
+    }
+    #[inline]
+    fn rollback(&mut self, checkpoint: usize) {
+        self.truncate(checkpoint);
+    }
 }
 
 #[cfg(feature = "tinyvec")]
@@ -217,6 +639,56 @@ impl<const CAP: usize> SerWrite for tinyvec::TinyVec<[u8; CAP]>
         }
         Ok(())
     }
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> SerResult<()> {
+        self.try_reserve(bufs.iter().map(|buf| buf.len()).sum())?;
+        for buf in bufs {
+            match self {
+              tinyvec::TinyVec::Inline(a) => a.extend_from_slice(buf),
+              tinyvec::TinyVec::Heap(h) => h.extend_from_slice(buf),
+            }
+        }
+        Ok(())
+    }
+    #[inline]
+    fn reserve(&mut self, additional: usize) -> SerResult<()> {
+        Ok(self.try_reserve(additional)?)
+    }
+}
+
+#[cfg(feature = "tinyvec")]
+macro_rules! implement_tinyvec_checkpoint {
+    () => {
+        #[inline]
+        fn checkpoint(&self) -> usize {
+            self.len()
+        }
+        #[inline]
+        fn rollback(&mut self, checkpoint: usize) {
+            self.truncate(checkpoint);
+        }
+    };
+}
+
+#[cfg(feature = "tinyvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tinyvec")))]
+impl<const CAP: usize> SerCheckpoint for tinyvec::ArrayVec<[u8; CAP]>
+    where [u8; CAP]: tinyvec::Array<Item=u8>,
+{
+    implement_tinyvec_checkpoint!{}
+}
+
+#[cfg(feature = "tinyvec")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tinyvec")))]
+impl SerCheckpoint for tinyvec::SliceVec<'_, u8> {
+    implement_tinyvec_checkpoint!{}
+}
+
+#[cfg(all(feature = "tinyvec", any(feature = "std", feature = "alloc")))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "tinyvec", any(feature = "std", feature = "alloc")))))]
+impl<const CAP: usize> SerCheckpoint for tinyvec::TinyVec<[u8; CAP]>
+    where [u8; CAP]: tinyvec::Array<Item=u8>
+{
+    implement_tinyvec_checkpoint!{}
 }
 
 #[cfg(test)]
@@ -232,6 +704,99 @@ mod tests {
         unsafe { core::slice::from_raw_parts(ptr, oversize) }
     }
 
+    #[cfg(any(feature = "std", feature = "alloc", feature = "arrayvec",
+              feature = "heapless", feature = "smallvec", feature = "tinyvec"))]
+    fn test_ser_checkpoint_impl<W: SerCheckpoint + AsRef<[u8]> + ?Sized>(writer: &mut W)
+        where W::Error: core::fmt::Debug
+    {
+        writer.write(b"Hello World!").unwrap();
+        let cp = writer.checkpoint();
+        assert_eq!(cp, 12);
+        writer.write_str(" Good Bye!").unwrap();
+        assert_eq!(writer.as_ref(), b"Hello World! Good Bye!");
+        writer.rollback(cp);
+        assert_eq!(writer.as_ref(), b"Hello World!");
+        assert_eq!(writer.checkpoint(), 12);
+        writer.rollback(0);
+        assert_eq!(writer.as_ref(), b"");
+    }
+
+    #[test]
+    fn test_ser_checkpoint() {
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        {
+            test_ser_checkpoint_impl(&mut Vec::new());
+            test_ser_checkpoint_impl(&mut String::new());
+            let mut writer = VecDeque::new();
+            writer.write(b"Hello World!").unwrap();
+            let cp = writer.checkpoint();
+            writer.write_str(" Good Bye!").unwrap();
+            writer.rollback(cp);
+            assert_eq!(writer, b"Hello World!");
+        }
+        #[cfg(feature = "arrayvec")]
+        {
+            test_ser_checkpoint_impl(&mut arrayvec::ArrayVec::<u8,22>::new());
+            let mut writer = arrayvec::ArrayString::<22>::new();
+            writer.write(b"Hello World!").unwrap();
+            let cp = writer.checkpoint();
+            writer.write_str(" Good Bye!").unwrap();
+            writer.rollback(cp);
+            assert_eq!(&writer, "Hello World!");
+        }
+        #[cfg(feature = "heapless")]
+        {
+            test_ser_checkpoint_impl(&mut heapless::Vec::<u8,22>::new());
+            let writer: &mut heapless::VecView<u8> = &mut heapless::Vec::<u8,22>::new();
+            test_ser_checkpoint_impl(writer);
+            test_ser_checkpoint_impl(&mut heapless::String::<22>::new());
+            let writer: &mut heapless::string::StringView = &mut heapless::String::<22>::new();
+            test_ser_checkpoint_impl(writer);
+            let mut writer = heapless::Deque::<u8,22>::new();
+            writer.write(b"Hello World!").unwrap();
+            let cp = writer.checkpoint();
+            writer.write_str(" Good Bye!").unwrap();
+            writer.rollback(cp);
+            assert!(writer.iter().eq(b"Hello World!"));
+        }
+        #[cfg(feature = "smallvec")]
+        test_ser_checkpoint_impl(&mut smallvec::SmallVec::<[u8;12]>::new());
+        #[cfg(feature = "tinyvec")]
+        {
+            test_ser_checkpoint_impl(&mut tinyvec::ArrayVec::<[u8; 22]>::new());
+            let mut buf = [0u8;22];
+            test_ser_checkpoint_impl(&mut tinyvec::SliceVec::from_slice_len(&mut buf, 0));
+        }
+        #[cfg(all(feature = "tinyvec", any(feature = "std", feature = "alloc")))]
+        test_ser_checkpoint_impl(&mut tinyvec::TinyVec::<[u8; 12]>::new());
+    }
+
+    fn test_ser_read_impl<R: SerRead>(mut reader: R) -> R
+        where R::Error: core::fmt::Debug
+    {
+        assert_eq!(reader.peek().unwrap(), Some(b'H'));
+        assert_eq!(reader.read_byte().unwrap(), Some(b'H'));
+        let mut buf = [0u8;10];
+        assert_eq!(reader.read(&mut buf).unwrap(), 10);
+        assert_eq!(&buf, b"ello World");
+        assert_eq!(reader.fill_buf().unwrap()[0], b'!');
+        reader.consume(1);
+        assert_eq!(reader.read(&mut buf).unwrap(), 10);
+        assert_eq!(&buf, b" Good Bye!");
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+        assert_eq!(reader.fill_buf().unwrap(), &[]);
+        assert_eq!(reader.peek().unwrap(), None);
+        assert_eq!(reader.read_byte().unwrap(), None);
+        reader
+    }
+
+    #[test]
+    fn test_ser_read_slice() {
+        let mut reader: &[u8] = b"Hello World! Good Bye!";
+        test_ser_read_impl(&mut reader);
+        assert!(reader.is_empty());
+    }
+
     #[cfg(any(feature = "std", feature = "alloc"))]
     #[test]
     fn test_ser_write_vec() {
@@ -241,6 +806,11 @@ mod tests {
         writer.write_str("Good Bye!").unwrap();
         let expected = b"Hello World! Good Bye!";
         assert_eq!(&writer, expected);
+        SerWrite::reserve(&mut writer, 10).unwrap();
+        assert!(writer.capacity() >= 32);
+        writer.write_vectored(&[b" ", b"", b"Hello", b" again!"]).unwrap();
+        assert_eq!(&writer, b"Hello World! Good Bye! Hello again!");
+        assert_eq!(SerWrite::reserve(&mut writer, usize::MAX), Err(SerError::BufferFull));
         unsafe {
             assert_eq!(writer.write(oversize_bytes()).unwrap_err(), SerError::BufferFull);
         }
@@ -260,6 +830,36 @@ mod tests {
         }
     }
 
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn test_ser_read_vec_deque() {
+        let mut reader = VecDeque::with_capacity(22);
+        reader.extend(b"d Bye!".iter().copied());
+        for &c in b"Hello World! Goo".iter().rev() {
+            reader.push_front(c);
+        }
+        assert_eq!(reader, b"Hello World! Good Bye!");
+        let reader = test_ser_read_impl(reader);
+        assert!(reader.is_empty());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_ser_read_cursor() {
+        let reader = Cursor::new(b"Hello World! Good Bye!");
+        let mut reader = test_ser_read_impl(reader);
+        assert_eq!(reader.position(), 22);
+        reader.set_position(100);
+        assert_eq!(reader.fill_buf().unwrap(), &[]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_ser_read_buf_reader() {
+        let reader = std::io::BufReader::with_capacity(4, &b"Hello World! Good Bye!"[..]);
+        test_ser_read_impl(reader);
+    }
+
     #[cfg(feature = "std")]
     #[test]
     fn test_ser_write_cursor() {
@@ -288,6 +888,48 @@ mod tests {
         writer.write(b"").unwrap();
     }
 
+    #[cfg(any(feature = "std", feature = "alloc", feature = "arrayvec", feature = "heapless"))]
+    fn test_ser_write_string_impl<W>(writer: &mut W)
+        where W: SerWrite<Error=SerError> + core::ops::Deref<Target=str> + ?Sized
+    {
+        writer.write(b"Hello World!").unwrap();
+        writer.write_byte(b' ').unwrap();
+        writer.write_str("Zażółć").unwrap();
+        assert_eq!(writer.write(&"ę".as_bytes()[..1]), Err(SerError::InvalidUtf8));
+        assert_eq!(writer.write_byte(0xC4), Err(SerError::InvalidUtf8));
+        writer.write_vectored(&[b"", "!".as_bytes()]).unwrap();
+        assert_eq!(&**writer, "Hello World! Zażółć!");
+    }
+
+    #[cfg(any(feature = "std", feature = "alloc"))]
+    #[test]
+    fn test_ser_write_string() {
+        let mut writer = String::new();
+        test_ser_write_string_impl(&mut writer);
+        SerWrite::reserve(&mut writer, 10).unwrap();
+        assert!(writer.capacity() >= 34);
+        assert_eq!(SerWrite::reserve(&mut writer, usize::MAX), Err(SerError::BufferFull));
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn test_ser_write_arraystring() {
+        let mut writer = arrayvec::ArrayString::<24>::new();
+        test_ser_write_string_impl(&mut writer);
+        assert_eq!(writer.write_byte(b' ').unwrap_err(), SerError::BufferFull);
+        assert_eq!(writer.write_str(" ").unwrap_err(), SerError::BufferFull);
+        writer.write(b"").unwrap();
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn test_ser_read_arrayvec() {
+        let mut reader = arrayvec::ArrayVec::<u8,22>::new();
+        reader.write(b"Hello World! Good Bye!").unwrap();
+        let reader = test_ser_read_impl(reader);
+        assert!(reader.is_empty());
+    }
+
     #[cfg(feature = "heapless")]
     #[test]
     fn test_ser_write_heapless() {
@@ -315,6 +957,58 @@ mod tests {
         test_ser_write_heapless_impl::<usize>();
         test_ser_write_heapless_impl::<u8>();
         test_ser_write_heapless_impl::<u16>();
+
+        let mut writer = heapless::String::<24>::new();
+        test_ser_write_string_impl(&mut writer);
+        assert_eq!(writer.write_byte(b' ').unwrap_err(), SerError::BufferFull);
+        assert_eq!(writer.write_str(" ").unwrap_err(), SerError::BufferFull);
+        let writer: &mut heapless::string::StringView<u8> = &mut heapless::String::<24, u8>::new();
+        test_ser_write_string_impl(writer);
+        assert_eq!(writer.write(b" ").unwrap_err(), SerError::BufferFull);
+
+        let mut writer = heapless::Deque::<u8,22>::new();
+        writer.write(b"Hello ").unwrap();
+        for _ in 0..6 {
+            writer.pop_front().unwrap();
+        }
+        writer.write(b"World!").unwrap();
+        writer.write_byte(b' ').unwrap();
+        writer.write_str("Good Bye! Hello").unwrap();
+        let (head, tail) = writer.as_slices();
+        assert!(head.iter().chain(tail).eq(b"World! Good Bye! Hello"));
+        assert!(!tail.is_empty());
+        assert_eq!(writer.write_byte(b' ').unwrap_err(), SerError::BufferFull);
+        writer.pop_front().unwrap();
+        assert_eq!(writer.write(b"  ").unwrap_err(), SerError::BufferFull);
+        assert_eq!(writer.len(), 21);
+        let writer: &mut heapless::deque::DequeView<u8> = writer.as_mut_view();
+        writer.write(b"!").unwrap();
+        writer.write(b"").unwrap();
+        assert_eq!(writer.write_byte(b'!').unwrap_err(), SerError::BufferFull);
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_ser_read_heapless() {
+        let mut reader = heapless::Vec::<u8,22>::new();
+        reader.write(b"Hello World! Good Bye!").unwrap();
+        let reader = test_ser_read_impl(reader);
+        assert!(reader.is_empty());
+
+        let reader: &mut heapless::VecView<u8> = &mut heapless::Vec::<u8,22>::new();
+        reader.write(b"Hello World! Good Bye!").unwrap();
+        let reader = test_ser_read_impl(reader);
+        assert!(reader.is_empty());
+
+        let mut reader = heapless::Deque::<u8,22>::new();
+        for &c in b"Good Bye!".iter() {
+            reader.push_back(c).unwrap();
+        }
+        for &c in b"Hello World! ".iter().rev() {
+            reader.push_front(c).unwrap();
+        }
+        let reader = test_ser_read_impl(reader);
+        assert!(reader.is_empty());
     }
 
     #[cfg(feature = "smallvec")]
@@ -326,6 +1020,11 @@ mod tests {
         writer.write_str("Good Bye!").unwrap();
         let expected = b"Hello World! Good Bye!";
         assert_eq!(writer.as_slice(), expected);
+        SerWrite::reserve(&mut writer, 10).unwrap();
+        assert!(writer.capacity() >= 32);
+        writer.write_vectored(&[b" ", b"", b"Hello", b" again!"]).unwrap();
+        assert_eq!(writer.as_slice(), b"Hello World! Good Bye! Hello again!");
+        assert_eq!(SerWrite::reserve(&mut writer, usize::MAX), Err(SerError::BufferFull));
         unsafe {
             assert_eq!(writer.write(oversize_bytes()).unwrap_err(), SerError::BufferFull);
         }
@@ -369,6 +1068,11 @@ mod tests {
         writer.write_str("Good Bye!").unwrap();
         let expected = b"Hello World! Good Bye!";
         assert_eq!(writer.as_slice(), expected);
+        SerWrite::reserve(&mut writer, 10).unwrap();
+        assert!(writer.capacity() >= 32);
+        writer.write_vectored(&[b" ", b"", b"Hello", b" again!"]).unwrap();
+        assert_eq!(writer.as_slice(), b"Hello World! Good Bye! Hello again!");
+        assert_eq!(SerWrite::reserve(&mut writer, usize::MAX), Err(SerError::BufferFull));
         unsafe {
             assert_eq!(writer.write(oversize_bytes()).unwrap_err(), SerError::BufferFull);
         }
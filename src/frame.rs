@@ -0,0 +1,286 @@
+//! Length-prefixed frame builder.
+use super::*;
+
+/// The format of a frame length header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LengthPrefix {
+    /// A single byte
+    U8,
+    /// 2 bytes, little-endian
+    U16Le,
+    /// 2 bytes, big-endian
+    U16Be,
+    /// 4 bytes, little-endian
+    U32Le,
+    /// 4 bytes, big-endian
+    U32Be,
+    /// An unsigned LEB128 variable-length integer with the given maximum number of bytes.
+    ///
+    /// The maximum must be in the range `1..=10`.
+    ///
+    /// The header space of the maximum width is reserved up front and the
+    /// payload is moved back when the frame is finished if the encoded length
+    /// is shorter.
+    Varint(u8)
+}
+
+impl LengthPrefix {
+    /// Return the number of bytes reserved for the header.
+    pub fn width(self) -> usize {
+        match self {
+            LengthPrefix::U8 => 1,
+            LengthPrefix::U16Le|LengthPrefix::U16Be => 2,
+            LengthPrefix::U32Le|LengthPrefix::U32Be => 4,
+            LengthPrefix::Varint(max) => max as usize
+        }
+    }
+    /// Encode `len` into `buf` returning the number of bytes used.
+    ///
+    /// Return `None` if the `len` doesn't fit in the header.
+    fn encode(self, len: usize, buf: &mut [u8; 10]) -> Option<usize> {
+        match self {
+            LengthPrefix::U8 => {
+                buf[0] = u8::try_from(len).ok()?;
+                Some(1)
+            }
+            LengthPrefix::U16Le => {
+                buf[..2].copy_from_slice(&u16::try_from(len).ok()?.to_le_bytes());
+                Some(2)
+            }
+            LengthPrefix::U16Be => {
+                buf[..2].copy_from_slice(&u16::try_from(len).ok()?.to_be_bytes());
+                Some(2)
+            }
+            LengthPrefix::U32Le => {
+                buf[..4].copy_from_slice(&u32::try_from(len).ok()?.to_le_bytes());
+                Some(4)
+            }
+            LengthPrefix::U32Be => {
+                buf[..4].copy_from_slice(&u32::try_from(len).ok()?.to_be_bytes());
+                Some(4)
+            }
+            LengthPrefix::Varint(max) => {
+                let mut len = len as u64;
+                let mut n = 0;
+                loop {
+                    let byte = buf.get_mut(..max as usize)?.get_mut(n)?;
+                    *byte = (len & 0x7F) as u8;
+                    len >>= 7;
+                    n += 1;
+                    if len == 0 {
+                        break Some(n)
+                    }
+                    *byte |= 0x80;
+                }
+            }
+        }
+    }
+}
+
+/// A frame builder writing a length header followed by the payload.
+///
+/// The header space is reserved when the builder is created and patched
+/// with the actual payload length by [`FrameBuilder::finish`].
+///
+/// If the `FrameBuilder` is dropped without finishing, the whole frame is rolled back.
+///
+/// Works on top of writers implementing [`SerCheckpoint`] with access to the written
+/// data via [`AsMut<[u8]>`][AsMut], e.g. [`SliceWriter`], `Vec<u8>`,
+/// `arrayvec::ArrayVec<u8, CAP>` or `heapless::Vec<u8, CAP>`.
+pub struct FrameBuilder<'a, W: SerCheckpoint + AsMut<[u8]>> {
+    writer: &'a mut W,
+    prefix: LengthPrefix,
+    start: usize,
+    finished: bool
+}
+
+impl<'a, W: SerCheckpoint + AsMut<[u8]>> FrameBuilder<'a, W> {
+    /// Create a new `FrameBuilder` reserving the header space in the `writer`.
+    ///
+    /// # Panics
+    /// Panics if the maximum width of [`LengthPrefix::Varint`] is not in the range `1..=10`.
+    pub fn new(writer: &'a mut W, prefix: LengthPrefix) -> Result<Self, W::Error> {
+        if let LengthPrefix::Varint(max) = prefix {
+            assert!((1..=10).contains(&max), "the varint width must be in the range 1..=10");
+        }
+        let start = writer.checkpoint();
+        let frame = FrameBuilder { writer, prefix, start, finished: false };
+        for _ in 0..prefix.width() {
+            frame.writer.write_byte(0)?;
+        }
+        Ok(frame)
+    }
+    /// Return the length of the payload written so far.
+    pub fn payload_len(&self) -> usize {
+        self.writer.checkpoint() - self.start - self.prefix.width()
+    }
+    /// Write the actual payload length to the header and return the length of the whole frame.
+    ///
+    /// If the payload length doesn't fit in the header, the whole frame is rolled
+    /// back and [`SerError::FrameTooLong`] is returned.
+    pub fn finish(mut self) -> SerResult<usize> {
+        let width = self.prefix.width();
+        let len = self.payload_len();
+        let mut header = [0u8; 10];
+        let hlen = self.prefix.encode(len, &mut header).ok_or(SerError::FrameTooLong)?;
+        let start = self.start;
+        let frame = &mut self.writer.as_mut()[start..];
+        frame[..hlen].copy_from_slice(&header[..hlen]);
+        if hlen < width {
+            frame.copy_within(width.., hlen);
+            self.writer.rollback(start + hlen + len);
+        }
+        self.finished = true;
+        Ok(hlen + len)
+    }
+}
+
+impl<W: SerCheckpoint + AsMut<[u8]>> Drop for FrameBuilder<'_, W> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.writer.rollback(self.start);
+        }
+    }
+}
+
+impl<W: SerCheckpoint + AsMut<[u8]>> SerWrite for FrameBuilder<'_, W> {
+    type Error = W::Error;
+
+    #[inline(always)]
+    fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.writer.write(buf)
+    }
+
+    #[inline(always)]
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::Error> {
+        self.writer.write_byte(byte)
+    }
+
+    #[inline(always)]
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.writer.write_str(s)
+    }
+
+    #[inline(always)]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        self.writer.write_vectored(bufs)
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) -> Result<(), Self::Error> {
+        self.writer.reserve(additional)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_frame_builder_impl<W>(writer: &mut W)
+        where W: SerCheckpoint + AsMut<[u8]>,
+              W::Error: core::fmt::Debug
+    {
+        for (prefix, expected) in [
+            (LengthPrefix::U8, &b"\x05Hello"[..]),
+            (LengthPrefix::U16Le, b"\x05\x00Hello"),
+            (LengthPrefix::U16Be, b"\x00\x05Hello"),
+            (LengthPrefix::U32Le, b"\x05\x00\x00\x00Hello"),
+            (LengthPrefix::U32Be, b"\x00\x00\x00\x05Hello"),
+            (LengthPrefix::Varint(3), b"\x05Hello"),
+        ] {
+            writer.rollback(0);
+            writer.write(b"<>").unwrap();
+            let mut frame = FrameBuilder::new(writer, prefix).unwrap();
+            assert_eq!(frame.payload_len(), 0);
+            frame.write(b"Hell").unwrap();
+            frame.write_byte(b'o').unwrap();
+            assert_eq!(frame.payload_len(), 5);
+            assert_eq!(frame.finish(), Ok(expected.len()));
+            assert_eq!(&writer.as_mut()[..2], b"<>");
+            assert_eq!(&writer.as_mut()[2..], expected);
+        }
+        writer.rollback(0);
+        let mut frame = FrameBuilder::new(writer, LengthPrefix::Varint(2)).unwrap();
+        for _ in 0..200 {
+            frame.write_byte(b'.').unwrap();
+        }
+        assert_eq!(frame.finish(), Ok(202));
+        assert_eq!(&writer.as_mut()[..3], b"\xC8\x01.");
+        assert_eq!(writer.as_mut().len(), 202);
+        {
+            let mut frame = FrameBuilder::new(writer, LengthPrefix::U8).unwrap();
+            frame.write(b"Good Bye!").unwrap();
+        }
+        assert_eq!(writer.as_mut().len(), 202);
+        let mut frame = FrameBuilder::new(writer, LengthPrefix::Varint(1)).unwrap();
+        for _ in 0..128 {
+            frame.write_byte(b'.').unwrap();
+        }
+        assert_eq!(frame.finish(), Err(SerError::FrameTooLong));
+        assert_eq!(writer.as_mut().len(), 202);
+        let mut frame = FrameBuilder::new(writer, LengthPrefix::U8).unwrap();
+        for _ in 0..256 {
+            frame.write_byte(b'.').unwrap();
+        }
+        assert_eq!(frame.finish(), Err(SerError::FrameTooLong));
+        assert_eq!(writer.as_mut().len(), 202);
+    }
+
+    #[test]
+    fn test_frame_builder() {
+        let mut buf = [0u8;512];
+        test_frame_builder_impl(&mut SliceWriter::new(&mut buf));
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        {
+            #[cfg(feature = "std")]
+            use std::vec::Vec;
+            #[cfg(not(feature = "std"))]
+            use alloc::vec::Vec;
+            test_frame_builder_impl(&mut Vec::new());
+        }
+        #[cfg(feature = "arrayvec")]
+        test_frame_builder_impl(&mut arrayvec::ArrayVec::<u8,512>::new());
+        #[cfg(feature = "heapless")]
+        test_frame_builder_impl(&mut heapless::Vec::<u8,512>::new());
+    }
+
+    #[test]
+    fn test_frame_builder_full() {
+        let mut buf = [0u8;4];
+        let mut writer = SliceWriter::new(&mut buf);
+        writer.write_byte(b'!').unwrap();
+        assert!(FrameBuilder::new(&mut writer, LengthPrefix::U32Be).is_err());
+        assert_eq!(writer.as_ref(), b"!");
+        let mut frame = FrameBuilder::new(&mut writer, LengthPrefix::U16Be).unwrap();
+        assert_eq!(frame.write(b"ab"), Err(SerError::BufferFull));
+        frame.write(b"a").unwrap();
+        assert_eq!(frame.finish(), Ok(3));
+        assert_eq!(writer.as_ref(), b"!\x00\x01a");
+    }
+
+    #[test]
+    #[should_panic(expected = "the varint width must be in the range 1..=10")]
+    fn test_frame_builder_varint_zero() {
+        let mut buf = [0u8;16];
+        let _ = FrameBuilder::new(&mut SliceWriter::new(&mut buf), LengthPrefix::Varint(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "the varint width must be in the range 1..=10")]
+    fn test_frame_builder_varint_too_wide() {
+        let mut buf = [0u8;16];
+        let _ = FrameBuilder::new(&mut SliceWriter::new(&mut buf), LengthPrefix::Varint(11));
+    }
+
+    #[test]
+    fn test_frame_builder_forwarding() {
+        let mut buf = [0u8;16];
+        let mut writer = crate::test_util::CallRecorder::new(&mut buf);
+        let mut frame = FrameBuilder::new(&mut writer, LengthPrefix::U8).unwrap();
+        frame.reserve(5).unwrap();
+        frame.write_vectored(&[b"Hel", b"lo"]).unwrap();
+        assert_eq!(frame.finish(), Ok(6));
+        assert_eq!((writer.vectored, writer.reserved), (1, 5));
+        assert_eq!(writer.writer.as_ref(), b"\x05Hello");
+    }
+}
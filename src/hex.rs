@@ -0,0 +1,108 @@
+//! HEX encoder.
+use super::*;
+
+#[inline(always)]
+fn hex_4bit(c: u8) -> u8 {
+    if c <= 9 {
+        0x30 + c
+    } else {
+        0x41 + (c - 10)
+    }
+}
+
+/// Encode a single byte as two upper-case ASCII HEX nibbles.
+#[inline(always)]
+pub fn encode_byte(c: u8) -> [u8;2] {
+    [hex_4bit(c >> 4), hex_4bit(c & 0x0F)]
+}
+
+/// Encode an array of bytes as upper-case ASCII HEX nibbles into a [`SerWrite`] implementing object.
+pub fn encode<W: SerWrite>(ser: &mut W, bytes: &[u8]) -> Result<(), W::Error> {
+    let mut output = [0u8;64];
+    for chunk in bytes.chunks(output.len() / 2) {
+        for (nibbles, &byte) in output.chunks_exact_mut(2).zip(chunk) {
+            nibbles.copy_from_slice(&encode_byte(byte));
+        }
+        ser.write(&output[..chunk.len() * 2])?;
+    }
+    Ok(())
+}
+
+/// A writer encoding data as upper-case ASCII HEX nibbles on the fly into the inner writer.
+///
+/// Data may be written in chunks of any size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HexWriter<W> {
+    inner: W
+}
+
+impl<W> HexWriter<W> {
+    /// Create a new instance
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        HexWriter { inner }
+    }
+    /// Provide read-only access to the inner writer.
+    #[inline]
+    pub fn writer_ref(&self) -> &W {
+        &self.inner
+    }
+    /// Return the inner writer.
+    ///
+    /// HEX encoding needs no padding, so this never fails.
+    #[inline]
+    pub fn finish(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: SerWrite> SerWrite for HexWriter<W> {
+    type Error = W::Error;
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        encode(&mut self.inner, buf)
+    }
+    #[inline]
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::Error> {
+        self.inner.write(&encode_byte(byte))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_encode() {
+        let mut buf = [0u8;6];
+        let writer = &mut SliceWriter::new(&mut buf);
+        encode(writer, &[]).unwrap();
+        assert_eq!(writer.as_ref(), b"");
+        encode(writer, &[0x00,0x9A,0xFF]).unwrap();
+        assert_eq!(writer.as_ref(), b"009AFF");
+        writer.clear();
+        assert_eq!(encode(writer, &[0;4]), Err(SerError::BufferFull));
+        let mut buf = [0u8;200];
+        let writer = &mut SliceWriter::new(&mut buf);
+        let bytes: [u8;100] = core::array::from_fn(|i| i as u8);
+        encode(writer, &bytes).unwrap();
+        assert_eq!(writer.len(), 200);
+        assert_eq!(&writer.as_ref()[60..68], b"1E1F2021");
+        assert_eq!(&writer.as_ref()[196..], b"6263");
+    }
+
+    #[test]
+    fn test_hex_writer() {
+        let mut buf = [0u8;12];
+        let mut writer = HexWriter::new(SliceWriter::new(&mut buf));
+        writer.write(b"\x01").unwrap();
+        writer.write_byte(0xAB).unwrap();
+        writer.write_str("Hi!").unwrap();
+        writer.write(b"").unwrap();
+        assert_eq!(writer.writer_ref().as_ref(), b"01AB486921");
+        assert_eq!(writer.write(b"\x00\x00"), Err(SerError::BufferFull));
+        writer.write_byte(0).unwrap();
+        assert_eq!(writer.finish().as_ref(), b"01AB48692100");
+    }
+}
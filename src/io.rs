@@ -0,0 +1,104 @@
+//! Adapters for `std::io` and `embedded-io` sinks.
+use super::*;
+
+/// An adapter implementing [`SerWrite`] for [`std::io::Write`] sinks,
+/// e.g. files, sockets or buffered writers.
+///
+/// The original [`std::io::Error`] is passed through on failure.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IoWriter<W>(pub W);
+
+#[cfg(feature = "std")]
+impl<W> IoWriter<W> {
+    /// Create a new instance
+    #[inline(always)]
+    pub fn new(inner: W) -> Self {
+        IoWriter(inner)
+    }
+    /// Destruct self returning the inner sink.
+    #[inline(always)]
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> SerWrite for IoWriter<W> {
+    type Error = std::io::Error;
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.0.write_all(buf)
+    }
+}
+
+/// An adapter implementing [`SerWrite`] for [`embedded_io::Write`] sinks,
+/// e.g. HAL serial ports.
+///
+/// The original error type of the sink is passed through on failure.
+#[cfg(feature = "embedded-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded-io")))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EmbeddedIoWriter<W>(pub W);
+
+#[cfg(feature = "embedded-io")]
+impl<W> EmbeddedIoWriter<W> {
+    /// Create a new instance
+    #[inline(always)]
+    pub fn new(inner: W) -> Self {
+        EmbeddedIoWriter(inner)
+    }
+    /// Destruct self returning the inner sink.
+    #[inline(always)]
+    pub fn into_inner(self) -> W {
+        self.0
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<W: embedded_io::Write> SerWrite for EmbeddedIoWriter<W> {
+    type Error = W::Error;
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.0.write_all(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_io_writer() {
+        let mut writer = IoWriter::new(std::io::BufWriter::new(std::vec::Vec::new()));
+        writer.write(b"Hello World!").unwrap();
+        writer.write_byte(b' ').unwrap();
+        writer.write_str("Good Bye!").unwrap();
+        let expected = b"Hello World! Good Bye!";
+        assert_eq!(writer.into_inner().into_inner().unwrap(), expected);
+
+        let mut buf = [0u8;12];
+        let mut writer = IoWriter::new(&mut buf[..]);
+        writer.write(b"Hello World!").unwrap();
+        let err = writer.write_byte(b' ').unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+        assert_eq!(&buf, b"Hello World!");
+    }
+
+    #[cfg(feature = "embedded-io")]
+    #[test]
+    fn test_embedded_io_writer() {
+        let mut buf = [0u8;22];
+        let mut writer = EmbeddedIoWriter::new(&mut buf[..]);
+        writer.write(b"Hello World!").unwrap();
+        writer.write_byte(b' ').unwrap();
+        writer.write_str("Good Bye!").unwrap();
+        assert_eq!(writer.write_byte(b' ').unwrap_err(), embedded_io::SliceWriteError::Full);
+        assert_eq!(&buf, b"Hello World! Good Bye!");
+    }
+}
@@ -6,9 +6,15 @@
 //!
 //! Embedded or otherwise `no_std` projects can implement [`SerWrite`] for custom sinks.
 //!
+//! A complementary [trait][SerRead] for byte-oriented sources, akin to `std::io::BufRead`,
+//! is provided for deserializers reading data from other than contiguous slices.
+//!
 //! Some [implemenentations] for foreign types are provided depending on the enabled features.
 //!
 //! [implemenentations]: SerWrite#foreign-impls
+//!
+//! [`SerRead`] is implemented for `&[u8]` and, depending on the enabled features, for
+//! some [foreign types][SerRead#foreign-impls] as well.
 #![no_std]
 #![cfg_attr(not(test), forbid(unsafe_code))]
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -22,6 +28,54 @@ extern crate alloc;
 use core::fmt;
 
 mod foreign;
+mod array;
+mod chain;
+mod checkpoint;
+mod counting;
+mod dyn_write;
+mod flushing;
+mod frame;
+mod limit;
+mod ring;
+mod window;
+pub mod base64;
+pub mod checksum;
+pub mod cobs;
+pub mod hex;
+pub mod lzss;
+pub mod slip;
+#[cfg(any(test, feature = "test-util"))]
+#[doc(hidden)]
+pub mod test_util;
+#[cfg(any(feature = "std", feature = "embedded-io"))]
+mod io;
+#[cfg(feature = "async")]
+mod async_write;
+#[cfg(feature = "embedded-storage")]
+mod storage;
+
+pub use array::*;
+pub use chain::*;
+pub use checkpoint::*;
+pub use counting::*;
+pub use dyn_write::*;
+pub use flushing::*;
+pub use frame::*;
+pub use limit::*;
+pub use ring::*;
+pub use window::*;
+pub use base64::Base64Writer;
+pub use checksum::ChecksumWriter;
+pub use cobs::CobsWriter;
+pub use hex::HexWriter;
+pub use lzss::LzssWriter;
+pub use slip::SlipWriter;
+#[cfg(any(feature = "std", feature = "embedded-io"))]
+pub use io::*;
+#[cfg(feature = "async")]
+pub use async_write::*;
+#[cfg(feature = "embedded-storage")]
+pub use storage::*;
 
 pub type SerResult<T> = Result<T, SerError>;
 
@@ -31,12 +85,30 @@ pub type SerResult<T> = Result<T, SerError>;
 pub enum SerError {
     /// Buffer is full
     BufferFull,
+    /// Frame length exceeds the capacity of its header
+    FrameTooLong,
+    /// Encoded frame is malformed
+    InvalidFrame,
+    /// Underlying writer failed, see [`DynWriter`]
+    WriterFailed,
+    /// Write limit would be exceeded, converted from [`LimitError::Exceeded`]
+    LimitExceeded {
+        /// The maximum number of bytes allowed
+        limit: usize
+    },
+    /// Data written to a string container is not a complete UTF-8 sequence
+    InvalidUtf8,
 }
 
 impl fmt::Display for SerError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             SerError::BufferFull => f.write_str("buffer is full"),
+            SerError::FrameTooLong => f.write_str("frame is too long"),
+            SerError::InvalidFrame => f.write_str("invalid frame encoding"),
+            SerError::WriterFailed => f.write_str("underlying writer failed"),
+            SerError::LimitExceeded { limit } => write!(f, "write limit of {} bytes exceeded", limit),
+            SerError::InvalidUtf8 => f.write_str("invalid UTF-8 data"),
         }
     }
 }
@@ -65,9 +137,28 @@ pub trait SerWrite {
     fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
         self.write(s.as_bytes())
     }
+    /// Write **all** bytes from all `bufs` in order to the internal buffer.
+    ///
+    /// Writers for which each call has a significant overhead should override this
+    /// method. The default implementation calls [`SerWrite::write`] for each buffer.
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        for buf in bufs {
+            self.write(buf)?;
+        }
+        Ok(())
+    }
+    /// Hint that at least `additional` more bytes are about to be written.
+    ///
+    /// Growable buffers may allocate room for them in advance. The default
+    /// implementation does nothing.
+    #[inline]
+    fn reserve(&mut self, _additional: usize) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
 
-impl<T: SerWrite> SerWrite for &'_ mut T {
+impl<T: SerWrite + ?Sized> SerWrite for &'_ mut T {
     type Error = T::Error;
 
     #[inline(always)]
@@ -84,6 +175,100 @@ impl<T: SerWrite> SerWrite for &'_ mut T {
     fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
         (*self).write_str(s)
     }
+
+    #[inline(always)]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        (*self).write_vectored(bufs)
+    }
+
+    #[inline(always)]
+    fn reserve(&mut self, additional: usize) -> Result<(), Self::Error> {
+        (*self).reserve(additional)
+    }
+}
+
+/// Deserializers should read data from the implementations of this trait.
+///
+/// A byte-oriented source, akin to `std::io::BufRead`. Implementations
+/// expose their internally buffered data with [`SerRead::fill_buf`] and
+/// advance past the processed bytes with [`SerRead::consume`].
+pub trait SerRead {
+    /// An error type returned from the trait methods.
+    type Error;
+    /// Return the contents of the internal buffer, filling it with more data
+    /// from the source if it is empty.
+    ///
+    /// An empty slice is returned only when the source is exhausted.
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error>;
+    /// Mark `amt` bytes returned from [`SerRead::fill_buf`] as consumed.
+    ///
+    /// `amt` should not exceed the length of the slice returned by the last
+    /// call to [`SerRead::fill_buf`].
+    fn consume(&mut self, amt: usize);
+    /// Return the next byte without consuming it.
+    ///
+    /// Return `None` when the source is exhausted.
+    #[inline]
+    fn peek(&mut self) -> Result<Option<u8>, Self::Error> {
+        Ok(self.fill_buf()?.first().copied())
+    }
+    /// Read and consume a single byte.
+    ///
+    /// Return `None` when the source is exhausted.
+    #[inline]
+    fn read_byte(&mut self) -> Result<Option<u8>, Self::Error> {
+        let res = self.peek()?;
+        if res.is_some() {
+            self.consume(1);
+        }
+        Ok(res)
+    }
+    /// Read bytes into `buf` until it's full or the source is exhausted.
+    ///
+    /// Return the number of bytes read.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        let mut len = 0;
+        while len < buf.len() {
+            let chunk = self.fill_buf()?;
+            if chunk.is_empty() {
+                break
+            }
+            let amt = chunk.len().min(buf.len() - len);
+            buf[len..len + amt].copy_from_slice(&chunk[..amt]);
+            self.consume(amt);
+            len += amt;
+        }
+        Ok(len)
+    }
+}
+
+impl<T: SerRead + ?Sized> SerRead for &'_ mut T {
+    type Error = T::Error;
+
+    #[inline(always)]
+    fn fill_buf(&mut self) -> Result<&[u8], Self::Error> {
+        (**self).fill_buf()
+    }
+
+    #[inline(always)]
+    fn consume(&mut self, amt: usize) {
+        (**self).consume(amt)
+    }
+
+    #[inline(always)]
+    fn peek(&mut self) -> Result<Option<u8>, Self::Error> {
+        (**self).peek()
+    }
+
+    #[inline(always)]
+    fn read_byte(&mut self) -> Result<Option<u8>, Self::Error> {
+        (**self).read_byte()
+    }
+
+    #[inline(always)]
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        (**self).read(buf)
+    }
 }
 
 /// A simple slice writer (example implementation)
@@ -175,6 +360,11 @@ mod tests {
         #[cfg(feature = "std")]
         {
             assert_eq!(std::format!("{}", SerError::BufferFull), "buffer is full");
+            assert_eq!(std::format!("{}", SerError::FrameTooLong), "frame is too long");
+            assert_eq!(std::format!("{}", SerError::InvalidFrame), "invalid frame encoding");
+            assert_eq!(std::format!("{}", SerError::WriterFailed), "underlying writer failed");
+            assert_eq!(std::format!("{}", SerError::LimitExceeded { limit: 10 }), "write limit of 10 bytes exceeded");
+            assert_eq!(std::format!("{}", SerError::InvalidUtf8), "invalid UTF-8 data");
         }
         let mut buf = [0u8;0];
         let mut writer = SliceWriter::new(&mut buf);
@@ -209,4 +399,16 @@ mod tests {
         assert_eq!(head, expected);
         assert_eq!(writer.write_byte(b' ').unwrap_err(), SerError::BufferFull);
     }
+
+    #[test]
+    fn test_ser_write_vectored() {
+        let mut buf = [0u8;12];
+        let mut writer = SliceWriter::new(&mut buf);
+        writer.reserve(100).unwrap();
+        writer.write_vectored(&[b"Hello", b"", b" ", b"World!"]).unwrap();
+        assert_eq!(writer.as_ref(), b"Hello World!");
+        writer.clear();
+        assert_eq!(writer.write_vectored(&[b"Hello", b" World!!"]), Err(SerError::BufferFull));
+        assert_eq!(writer.as_ref(), b"Hello");
+    }
 }
@@ -0,0 +1,199 @@
+//! A writer limiting the number of bytes written.
+use super::*;
+
+/// A writer allowing at most `limit` bytes to be written to the inner writer.
+///
+/// A write which would exceed the limit returns [`LimitError::Exceeded`] and leaves
+/// the inner writer untouched. Errors of the inner writer are wrapped in [`LimitError::Writer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitWriter<W> {
+    inner: W,
+    limit: usize,
+    count: usize
+}
+
+impl<W> LimitWriter<W> {
+    /// Create a new instance allowing at most `limit` bytes to be written to the `inner` writer.
+    #[inline]
+    pub fn new(inner: W, limit: usize) -> Self {
+        LimitWriter { inner, limit, count: 0 }
+    }
+    /// Return the maximum number of bytes allowed.
+    #[inline]
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+    /// Return the number of bytes written so far.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+    /// Return the number of bytes which can still be written.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.limit - self.count
+    }
+    /// Reset the counter to zero, e.g. before writing the next record.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.count = 0;
+    }
+    /// Provide access to the inner writer.
+    #[inline]
+    pub fn writer(&mut self) -> &mut W {
+        &mut self.inner
+    }
+    /// Provide read-only access to the inner writer.
+    #[inline]
+    pub fn writer_ref(&self) -> &W {
+        &self.inner
+    }
+    /// Destruct self returning the inner writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+    #[inline]
+    fn take<E>(&mut self, len: usize) -> Result<(), LimitError<E>> {
+        if len > self.remaining() {
+            return Err(LimitError::Exceeded { limit: self.limit })
+        }
+        Ok(())
+    }
+}
+
+/// An error returned from [`LimitWriter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LimitError<E> {
+    /// Write limit would be exceeded
+    Exceeded {
+        /// The maximum number of bytes allowed
+        limit: usize
+    },
+    /// An error from the inner writer
+    Writer(E),
+}
+
+impl<E: fmt::Display> fmt::Display for LimitError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitError::Exceeded { limit } => write!(f, "write limit of {} bytes exceeded", limit),
+            LimitError::Writer(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<E: fmt::Display + fmt::Debug> core::error::Error for LimitError<E> {}
+
+impl From<LimitError<SerError>> for SerError {
+    fn from(err: LimitError<SerError>) -> SerError {
+        match err {
+            LimitError::Exceeded { limit } => SerError::LimitExceeded { limit },
+            LimitError::Writer(err) => err
+        }
+    }
+}
+
+impl<W: SerWrite> SerWrite for LimitWriter<W> {
+    type Error = LimitError<W::Error>;
+
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        self.take(buf.len())?;
+        self.inner.write(buf).map_err(LimitError::Writer)?;
+        self.count += buf.len();
+        Ok(())
+    }
+    #[inline]
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::Error> {
+        self.take(1)?;
+        self.inner.write_byte(byte).map_err(LimitError::Writer)?;
+        self.count += 1;
+        Ok(())
+    }
+    #[inline]
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.take(s.len())?;
+        self.inner.write_str(s).map_err(LimitError::Writer)?;
+        self.count += s.len();
+        Ok(())
+    }
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), Self::Error> {
+        let len = bufs.iter().map(|buf| buf.len()).sum();
+        self.take(len)?;
+        self.inner.write_vectored(bufs).map_err(LimitError::Writer)?;
+        self.count += len;
+        Ok(())
+    }
+    #[inline]
+    fn reserve(&mut self, additional: usize) -> Result<(), Self::Error> {
+        self.inner.reserve(additional.min(self.remaining())).map_err(LimitError::Writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_writer() {
+        let mut buf = [0u8;16];
+        let mut writer = LimitWriter::new(SliceWriter::new(&mut buf), 12);
+        assert_eq!(writer.limit(), 12);
+        writer.write(b"Hello").unwrap();
+        writer.write_byte(b' ').unwrap();
+        assert_eq!(writer.write_str("World!!"), Err(LimitError::Exceeded { limit: 12 }));
+        assert_eq!(writer.writer_ref().as_ref(), b"Hello ");
+        writer.write_str("World!").unwrap();
+        assert_eq!(writer.count(), 12);
+        assert_eq!(writer.remaining(), 0);
+        assert_eq!(writer.write_byte(b'!'), Err(LimitError::Exceeded { limit: 12 }));
+        assert_eq!(writer.write(b"!"), Err(LimitError::Exceeded { limit: 12 }));
+        writer.write(b"").unwrap();
+        assert_eq!(writer.writer_ref().as_ref(), b"Hello World!");
+        writer.reset();
+        assert_eq!(writer.write(b"Bye!!"), Err(LimitError::Writer(SerError::BufferFull)));
+        assert_eq!(writer.count(), 0);
+        writer.write(b"Bye!").unwrap();
+        writer.writer().clear();
+        writer.write(b"Bye!").unwrap();
+        assert_eq!(writer.write_vectored(&[b"12", b"345678", b"9"]), Err(LimitError::Exceeded { limit: 12 }));
+        assert_eq!(writer.writer_ref().as_ref(), b"Bye!");
+        writer.write_vectored(&[b"12", b"34"]).unwrap();
+        assert_eq!(writer.count(), 12);
+        assert_eq!(writer.writer_ref().as_ref(), b"Bye!1234");
+        writer.writer().clear();
+        assert!(writer.into_inner().is_empty());
+
+        #[cfg(any(feature = "std", feature = "alloc"))]
+        {
+            #[cfg(feature = "std")]
+            use std::vec::Vec;
+            #[cfg(not(feature = "std"))]
+            use alloc::vec::Vec;
+            let mut writer = LimitWriter::new(Vec::new(), 4);
+            writer.write(b"abc").unwrap();
+            assert_eq!(writer.write(b"de"), Err(LimitError::Exceeded { limit: 4 }));
+            assert_eq!(writer.into_inner(), b"abc");
+        }
+    }
+
+    #[test]
+    fn test_limit_error() {
+        let err: SerError = LimitError::Exceeded { limit: 3 }.into();
+        assert_eq!(err, SerError::LimitExceeded { limit: 3 });
+        let err: SerError = LimitError::Writer(SerError::BufferFull).into();
+        assert_eq!(err, SerError::BufferFull);
+        #[cfg(feature = "std")]
+        {
+            use std::format;
+            assert_eq!(format!("{}", LimitError::<SerError>::Exceeded { limit: 10 }), "write limit of 10 bytes exceeded");
+            assert_eq!(format!("{}", LimitError::Writer(SerError::BufferFull)), "buffer is full");
+            let mut writer = LimitWriter::new(IoWriter::new(std::vec::Vec::new()), 4);
+            writer.write(b"abcd").unwrap();
+            assert!(matches!(writer.write_byte(b'e'), Err(LimitError::Exceeded { limit: 4 })));
+            assert_eq!(writer.into_inner().into_inner(), b"abcd");
+        }
+    }
+}
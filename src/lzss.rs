@@ -0,0 +1,404 @@
+//! LZSS compression.
+//!
+//! The compressed stream consists of groups of up to 8 items, each group preceded by
+//! a flag byte. Bits of the flag byte, starting from the least significant one, tell
+//! whether the corresponding item is a literal byte (`1`) or a 2-byte back reference (`0`).
+//!
+//! A back reference encodes the distance `1..=4096` in its upper 12 bits and the length
+//! `3..=18` of the repeated sequence in its lower 4 bits, both biased to start from `0`.
+//! The referenced sequence may overlap the data being decoded.
+use super::*;
+
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 18;
+const MAX_WINDOW: usize = 4096;
+const GROUP_ITEMS: u8 = 8;
+/// The number of hash chain heads, indexed by a hash of 3 bytes.
+const HASH_HEADS: usize = 256;
+/// The maximum number of earlier positions probed when searching for a match.
+const MAX_PROBES: usize = 64;
+
+/// Hash the first `MIN_MATCH` bytes of a sequence.
+#[inline]
+fn hash(bytes: [u8; MIN_MATCH]) -> usize {
+    let [a, b, c] = bytes;
+    (u32::from_le_bytes([a, b, c, 0]).wrapping_mul(0x9E37_79B1) >> 24) as usize
+}
+
+/// A ring buffer holding the last `N` bytes of the uncompressed stream.
+#[derive(Debug, Clone)]
+struct History<const N: usize> {
+    buf: [u8; N],
+    head: usize,
+    filled: usize
+}
+
+impl<const N: usize> History<N> {
+    const fn new() -> Self {
+        History { buf: [0; N], head: 0, filled: 0 }
+    }
+    /// Return the byte `dist` bytes back from the end, `dist` must be in `1..=filled`.
+    #[inline]
+    fn back(&self, dist: usize) -> u8 {
+        self.buf[(self.head + N - dist) % N]
+    }
+    #[inline]
+    fn push(&mut self, byte: u8) {
+        self.buf[self.head] = byte;
+        self.head = (self.head + 1) % N;
+        self.filled = N.min(self.filled + 1);
+    }
+}
+
+/// A writer compressing data with LZSS on the fly into the inner writer.
+///
+/// `N` is the size of the sliding window in bytes, up to 4096. Larger windows find more
+/// repetitions at the cost of memory: besides the window, the writer holds a 2-byte hash
+/// chain link for each window position and 256 2-byte hash chain heads, so it takes
+/// about `3 * N + 512` bytes.
+///
+/// Matches are searched only among earlier positions starting with the same 3-byte hash,
+/// from the nearest one, and at most 64 of them are probed, so the time spent on each
+/// encoded item doesn't grow with `N`. This may miss some distant matches in highly
+/// repetitive data.
+///
+/// Call [`LzssWriter::finish`] to compress the remaining data.
+/// Use [`LzssDecoder`] with a window of at least `N` bytes to decompress the output.
+#[derive(Debug, Clone)]
+pub struct LzssWriter<W, const N: usize> {
+    inner: W,
+    history: History<N>,
+    /// The most recent position for each hash, as the wrapped count of bytes pushed to the history
+    heads: [u16; HASH_HEADS],
+    /// The previous position with the same hash for each position in the history window
+    links: [u16; N],
+    /// The wrapped count of bytes pushed to the history
+    pos: u16,
+    /// The number of the most recent positions not yet added to the hash chains
+    unhashed: usize,
+    ahead: [u8; MAX_MATCH],
+    ahead_len: usize,
+    group: [u8; 1 + 2 * GROUP_ITEMS as usize],
+    group_len: usize,
+    items: u8
+}
+
+impl<W, const N: usize> LzssWriter<W, N> {
+    /// Create a new instance
+    pub fn new(inner: W) -> Self {
+        const { assert!(N > 0 && N <= MAX_WINDOW, "the window size must be in the range 1..=4096") };
+        LzssWriter {
+            inner,
+            history: History::new(),
+            heads: [0; HASH_HEADS],
+            links: [0; N],
+            pos: 0,
+            unhashed: 0,
+            ahead: [0; MAX_MATCH],
+            ahead_len: 0,
+            group: [0; 1 + 2 * GROUP_ITEMS as usize],
+            group_len: 0,
+            items: 0
+        }
+    }
+    /// Provide read-only access to the inner writer.
+    #[inline]
+    pub fn writer_ref(&self) -> &W {
+        &self.inner
+    }
+    /// Return the byte at `index` of a sequence starting `dist` bytes back from the look-ahead.
+    #[inline]
+    fn source(&self, dist: usize, index: usize) -> u8 {
+        if index < dist {
+            self.history.back(dist - index)
+        }
+        else {
+            self.ahead[index - dist]
+        }
+    }
+    /// Return the index of the history window slot `dist` bytes back from the look-ahead.
+    #[inline]
+    fn slot(&self, dist: usize) -> usize {
+        (self.history.head + N - dist) % N
+    }
+    /// Add the most recent positions in the history window to the hash chains.
+    ///
+    /// The look-ahead must hold at least `MIN_MATCH` bytes.
+    fn hash_history(&mut self) {
+        for dist in (1..=self.unhashed.min(self.history.filled)).rev() {
+            let key = hash(core::array::from_fn(|index| self.source(dist, index)));
+            self.links[self.slot(dist)] = self.heads[key];
+            self.heads[key] = self.pos.wrapping_sub(dist as u16);
+        }
+        self.unhashed = 0;
+    }
+    /// Return the distance and the length of the longest match for the look-ahead data.
+    ///
+    /// Of the matches with the same length, the one with the shortest distance is chosen.
+    fn find_match(&mut self) -> (usize, usize) {
+        let mut best = (0, 0);
+        if self.ahead_len < MIN_MATCH {
+            return best
+        }
+        self.hash_history();
+        let key = hash([self.ahead[0], self.ahead[1], self.ahead[2]]);
+        let mut candidate = self.heads[key];
+        let mut last = 0;
+        for _ in 0..MAX_PROBES {
+            // stale entries are detected by the distance not increasing along the chain
+            let dist = usize::from(self.pos.wrapping_sub(candidate));
+            if dist <= last || dist > self.history.filled {
+                break
+            }
+            let len = (0..self.ahead_len)
+                .take_while(|&index| self.source(dist, index) == self.ahead[index])
+                .count();
+            if len > best.1 {
+                best = (dist, len);
+                if len == self.ahead_len {
+                    break
+                }
+            }
+            last = dist;
+            candidate = self.links[self.slot(dist)];
+        }
+        best
+    }
+}
+
+impl<W: SerWrite, const N: usize> LzssWriter<W, N> {
+    /// Compress the remaining data, write the last group and return the inner writer.
+    pub fn finish(mut self) -> Result<W, W::Error> {
+        while self.ahead_len != 0 {
+            self.encode_item()?;
+        }
+        self.write_group()?;
+        Ok(self.inner)
+    }
+    /// Encode the look-ahead data as a literal or a back reference.
+    fn encode_item(&mut self) -> Result<(), W::Error> {
+        let (dist, mut len) = self.find_match();
+        if self.items == 0 {
+            self.group[0] = 0;
+            self.group_len = 1;
+        }
+        if len >= MIN_MATCH {
+            let code = ((dist - 1) << 4) | (len - MIN_MATCH);
+            self.group[self.group_len..self.group_len + 2].copy_from_slice(&(code as u16).to_be_bytes());
+            self.group_len += 2;
+        }
+        else {
+            len = 1;
+            self.group[0] |= 1 << self.items;
+            self.group[self.group_len] = self.ahead[0];
+            self.group_len += 1;
+        }
+        for &byte in &self.ahead[..len] {
+            self.history.push(byte);
+        }
+        self.pos = self.pos.wrapping_add(len as u16);
+        self.unhashed += len;
+        self.ahead.copy_within(len..self.ahead_len, 0);
+        self.ahead_len -= len;
+        self.items += 1;
+        if self.items == GROUP_ITEMS {
+            self.write_group()?;
+        }
+        Ok(())
+    }
+    fn write_group(&mut self) -> Result<(), W::Error> {
+        if self.items != 0 {
+            self.items = 0;
+            self.inner.write(&self.group[..self.group_len])?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: SerWrite, const N: usize> SerWrite for LzssWriter<W, N> {
+    type Error = W::Error;
+
+    fn write(&mut self, mut buf: &[u8]) -> Result<(), Self::Error> {
+        while !buf.is_empty() {
+            let (head, tail) = buf.split_at((MAX_MATCH - self.ahead_len).min(buf.len()));
+            self.ahead[self.ahead_len..self.ahead_len + head.len()].copy_from_slice(head);
+            self.ahead_len += head.len();
+            if self.ahead_len == MAX_MATCH {
+                self.encode_item()?;
+            }
+            buf = tail;
+        }
+        Ok(())
+    }
+}
+
+/// A streaming LZSS decompressor with a sliding window of `N` bytes.
+///
+/// The window must not be smaller than the one used by the [`LzssWriter`].
+#[derive(Debug, Clone)]
+pub struct LzssDecoder<const N: usize> {
+    history: History<N>,
+    flags: u8,
+    items: u8,
+    pending: Option<u8>
+}
+
+impl<const N: usize> Default for LzssDecoder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> LzssDecoder<N> {
+    /// Create a new instance
+    pub const fn new() -> Self {
+        const { assert!(N > 0 && N <= MAX_WINDOW, "the window size must be in the range 1..=4096") };
+        LzssDecoder { history: History::new(), flags: 0, items: 0, pending: None }
+    }
+    /// Decompress a chunk of compressed `input` writing the decompressed data to the `output`.
+    ///
+    /// The compressed stream may be fed in chunks of any size.
+    ///
+    /// Return [`SerError::InvalidFrame`] converted to the writer's error type if the input
+    /// refers to data outside of the window.
+    pub fn feed<W>(&mut self, input: &[u8], output: &mut W) -> Result<(), W::Error>
+        where W: SerWrite + ?Sized, W::Error: From<SerError>
+    {
+        for &byte in input {
+            if self.items == 0 {
+                self.flags = byte;
+                self.items = GROUP_ITEMS;
+                continue
+            }
+            if let Some(hi) = self.pending.take() {
+                let code = usize::from(u16::from_be_bytes([hi, byte]));
+                let dist = (code >> 4) + 1;
+                let len = (code & 0x0F) + MIN_MATCH;
+                if dist > self.history.filled {
+                    return Err(SerError::InvalidFrame.into())
+                }
+                let mut chunk = [0u8; MAX_MATCH];
+                for target in &mut chunk[..len] {
+                    *target = self.history.back(dist);
+                    self.history.push(*target);
+                }
+                output.write(&chunk[..len])?;
+            }
+            else if self.flags & 1 == 0 {
+                self.pending = Some(byte);
+                continue
+            }
+            else {
+                self.history.push(byte);
+                output.write_byte(byte)?;
+            }
+            self.flags >>= 1;
+            self.items -= 1;
+        }
+        Ok(())
+    }
+    /// Validate the end of the compressed stream.
+    ///
+    /// Return [`SerError::InvalidFrame`] if the stream ended in the middle of a back reference.
+    pub fn finish(self) -> SerResult<()> {
+        match self.pending {
+            None => Ok(()),
+            Some(_) => Err(SerError::InvalidFrame)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TELEMETRY: &[u8] = br#"[{"id":1,"temp":21.5,"hum":40},{"id":2,"temp":21.5,"hum":41},{"id":3,"temp":21.4,"hum":41}]"#;
+
+    fn compress<'a, const N: usize>(buf: &'a mut [u8], data: &[u8], chunk: usize) -> &'a [u8] {
+        let mut writer = LzssWriter::<_, N>::new(SliceWriter::new(buf));
+        for part in data.chunks(chunk) {
+            writer.write(part).unwrap();
+        }
+        writer.finish().unwrap().split().0
+    }
+
+    fn decompress<'a, const N: usize>(buf: &'a mut [u8], data: &[u8], chunk: usize) -> &'a [u8] {
+        let mut writer = SliceWriter::new(buf);
+        let mut decoder = LzssDecoder::<N>::new();
+        for part in data.chunks(chunk) {
+            decoder.feed(part, &mut writer).unwrap();
+        }
+        decoder.finish().unwrap();
+        writer.split().0
+    }
+
+    #[test]
+    fn test_lzss_roundtrip() {
+        let mut cbuf = [0u8;128];
+        let mut dbuf = [0u8;128];
+        for chunk in [1, 3, 7, 18, 100] {
+            let compressed = compress::<256>(&mut cbuf, TELEMETRY, chunk);
+            assert!(compressed.len() < TELEMETRY.len() * 3 / 4);
+            for dchunk in [1, 2, 5, 100] {
+                assert_eq!(decompress::<256>(&mut dbuf, compressed, dchunk), TELEMETRY);
+                assert_eq!(decompress::<4096>(&mut dbuf, compressed, dchunk), TELEMETRY);
+            }
+            let compressed = compress::<16>(&mut cbuf, TELEMETRY, chunk);
+            assert_eq!(decompress::<16>(&mut dbuf, compressed, 3), TELEMETRY);
+        }
+        assert_eq!(compress::<16>(&mut cbuf, b"", 1), b"");
+        assert_eq!(decompress::<16>(&mut dbuf, b"", 1), b"");
+        assert_eq!(compress::<16>(&mut cbuf, b"ab", 1), b"\x03ab");
+    }
+
+    #[test]
+    fn test_lzss_overlapping_match() {
+        let mut cbuf = [0u8;16];
+        let mut dbuf = [0u8;64];
+        let data = [b'a'; 40];
+        let compressed = compress::<8>(&mut cbuf, &data, 40);
+        // a literal followed by back references at the distance of 1
+        assert_eq!(compressed, b"\x01a\x00\x0F\x00\x0F\x00\x00");
+        assert_eq!(decompress::<8>(&mut dbuf, compressed, 1), &data);
+    }
+
+    #[test]
+    fn test_lzss_distant_match() {
+        let mut data = [0u8; 4096 + 18];
+        let mut seed = 1u32;
+        for byte in &mut data[..4096] {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            *byte = (seed >> 16) as u8 & 0x0F;
+        }
+        data.copy_within(..18, 4096);
+        let mut cbuf = [0u8;5000];
+        let mut dbuf = [0u8;4096 + 18];
+        let compressed = compress::<4096>(&mut cbuf, &data, 100);
+        // the sequence at the start of the window is found at the maximum distance
+        let code = u16::from_be_bytes(compressed[compressed.len() - 2..].try_into().unwrap());
+        assert_eq!(code >> 4, 0xFFF);
+        assert_eq!(decompress::<4096>(&mut dbuf, compressed, 100), &data);
+        let compressed = compress::<256>(&mut cbuf, &data, 7);
+        assert_eq!(decompress::<256>(&mut dbuf, compressed, 13), &data);
+    }
+
+    #[test]
+    fn test_lzss_errors() {
+        let mut dbuf = [0u8;8];
+        let mut writer = SliceWriter::new(&mut dbuf);
+        let mut decoder = LzssDecoder::<16>::default();
+        assert_eq!(decoder.feed(b"\x01a\x00\x10", &mut writer), Err(SerError::InvalidFrame));
+        let mut decoder = LzssDecoder::<16>::new();
+        decoder.feed(b"\x01a\x00", &mut writer).unwrap();
+        assert_eq!(decoder.finish(), Err(SerError::InvalidFrame));
+        let mut decoder = LzssDecoder::<16>::new();
+        writer.clear();
+        assert_eq!(decoder.feed(b"\x01a\x00\x0F", &mut writer), Err(SerError::BufferFull));
+
+        let mut cbuf = [0u8;4];
+        let mut writer = LzssWriter::<_, 16>::new(SliceWriter::new(&mut cbuf));
+        writer.write(b"abcd").unwrap();
+        assert_eq!(writer.writer_ref().len(), 0);
+        assert_eq!(writer.finish().unwrap_err(), SerError::BufferFull);
+    }
+}
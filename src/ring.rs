@@ -0,0 +1,338 @@
+//! A lock-free single-producer single-consumer ring buffer.
+use core::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+#[cfg(target_has_atomic = "8")]
+use core::sync::atomic::AtomicBool;
+
+use super::*;
+
+/// A lock-free single-producer single-consumer byte ring buffer of `N` bytes.
+///
+/// [`RingBuffer::split`] it into a [`Producer`] and a [`Consumer`], which can be
+/// used from different execution contexts, e.g. the main loop and an interrupt handler.
+/// A ring buffer in a `static` can be split once with [`RingBuffer::try_split`] into
+/// halves with a `'static` lifetime.
+///
+/// The storage consists of atomic bytes, so the buffer can be shared without any unsafe code.
+pub struct RingBuffer<const N: usize> {
+    buf: [AtomicU8; N],
+    /// The total number of bytes consumed modulo `2 * N`
+    head: AtomicUsize,
+    /// The total number of bytes committed modulo `2 * N`
+    tail: AtomicUsize,
+    /// Whether the halves have been handed out by `try_split`
+    #[cfg(target_has_atomic = "8")]
+    taken: AtomicBool
+}
+
+impl<const N: usize> RingBuffer<N> {
+    /// Create a new, empty ring buffer.
+    pub const fn new() -> Self {
+        const { assert!(N > 0, "the ring buffer must not be empty") };
+        RingBuffer {
+            buf: [const { AtomicU8::new(0) }; N],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            #[cfg(target_has_atomic = "8")]
+            taken: AtomicBool::new(false)
+        }
+    }
+    /// Return the capacity of the ring buffer.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+    /// Return the number of committed bytes waiting to be consumed.
+    #[inline]
+    pub fn len(&self) -> usize {
+        let head = self.head.load(Ordering::Acquire);
+        Self::distance(head, self.tail.load(Ordering::Acquire))
+    }
+    /// Return whether there are no bytes waiting to be consumed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Split the ring buffer into a producer and a consumer.
+    pub fn split(&mut self) -> (Producer<'_, N>, Consumer<'_, N>) {
+        let ring = &*self;
+        (Producer { ring }, Consumer { ring })
+    }
+    /// Split a shared ring buffer into a producer and a consumer.
+    ///
+    /// The halves are handed out only once, subsequent calls return `None`.
+    /// Use it with a ring buffer in a `static` to obtain `'static` halves, e.g.
+    /// to move the producer to an interrupt handler.
+    ///
+    /// Available on targets supporting atomic swap operations.
+    #[cfg(target_has_atomic = "8")]
+    pub fn try_split(&self) -> Option<(Producer<'_, N>, Consumer<'_, N>)> {
+        if self.taken.swap(true, Ordering::AcqRel) {
+            return None
+        }
+        Some((Producer { ring: self }, Consumer { ring: self }))
+    }
+    #[inline(always)]
+    fn slot(&self, pos: usize) -> &AtomicU8 {
+        &self.buf[pos % N]
+    }
+    #[inline(always)]
+    fn advance(pos: usize, n: usize) -> usize {
+        (pos + n) % (2 * N)
+    }
+    #[inline(always)]
+    fn distance(from: usize, to: usize) -> usize {
+        (to + 2 * N - from) % (2 * N)
+    }
+}
+
+impl<const N: usize> Default for RingBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Debug for RingBuffer<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RingBuffer")
+         .field("capacity", &N)
+         .field("len", &self.len())
+         .finish()
+    }
+}
+
+/// The writing half of a [`RingBuffer`].
+///
+/// Writing via the `Producer` directly makes the data available to the [`Consumer`]
+/// after each write. Use [`Producer::reserve`] to make a record available only
+/// once it's complete.
+///
+/// A write either succeeds entirely or returns [`SerError::BufferFull`] without
+/// writing anything.
+#[derive(Debug)]
+pub struct Producer<'a, const N: usize> {
+    ring: &'a RingBuffer<N>
+}
+
+impl<'a, const N: usize> Producer<'a, N> {
+    /// Return the number of bytes which can be written.
+    #[inline]
+    pub fn free(&self) -> usize {
+        N - self.ring.len()
+    }
+    /// Start a new record which is made available to the [`Consumer`] only when
+    /// the returned [`Reservation`] is committed.
+    #[inline]
+    pub fn reserve(&mut self) -> Reservation<'_, 'a, N> {
+        let start = self.ring.tail.load(Ordering::Relaxed);
+        Reservation { producer: self, start, end: start }
+    }
+    /// Write `buf` at the `pos` if there's room for it, returning the new position.
+    fn write_at(&self, pos: usize, buf: &[u8]) -> SerResult<usize> {
+        let head = self.ring.head.load(Ordering::Acquire);
+        if N - RingBuffer::<N>::distance(head, pos) < buf.len() {
+            return Err(SerError::BufferFull)
+        }
+        for (i, &byte) in buf.iter().enumerate() {
+            self.ring.slot(RingBuffer::<N>::advance(pos, i)).store(byte, Ordering::Relaxed);
+        }
+        Ok(RingBuffer::<N>::advance(pos, buf.len()))
+    }
+}
+
+impl<const N: usize> SerWrite for Producer<'_, N> {
+    type Error = SerError;
+
+    fn write(&mut self, buf: &[u8]) -> SerResult<()> {
+        let tail = self.write_at(self.ring.tail.load(Ordering::Relaxed), buf)?;
+        self.ring.tail.store(tail, Ordering::Release);
+        Ok(())
+    }
+}
+
+/// A record being written to a [`RingBuffer`].
+///
+/// The written data is made available to the [`Consumer`] with [`Reservation::commit`].
+/// It's discarded when the `Reservation` is dropped without committing.
+#[derive(Debug)]
+pub struct Reservation<'p, 'a, const N: usize> {
+    producer: &'p mut Producer<'a, N>,
+    start: usize,
+    end: usize
+}
+
+impl<const N: usize> Reservation<'_, '_, N> {
+    /// Return the number of bytes written to the record so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        RingBuffer::<N>::distance(self.start, self.end)
+    }
+    /// Return whether nothing has been written to the record.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.end == self.start
+    }
+    /// Make the record available to the [`Consumer`].
+    #[inline]
+    pub fn commit(self) {
+        self.producer.ring.tail.store(self.end, Ordering::Release);
+    }
+    /// Discard the record.
+    ///
+    /// This is what happens when the `Reservation` is dropped without committing.
+    #[inline]
+    pub fn abort(self) {}
+}
+
+impl<const N: usize> SerWrite for Reservation<'_, '_, N> {
+    type Error = SerError;
+
+    fn write(&mut self, buf: &[u8]) -> SerResult<()> {
+        self.end = self.producer.write_at(self.end, buf)?;
+        Ok(())
+    }
+}
+
+/// The reading half of a [`RingBuffer`].
+#[derive(Debug)]
+pub struct Consumer<'a, const N: usize> {
+    ring: &'a RingBuffer<N>
+}
+
+impl<const N: usize> Consumer<'_, N> {
+    /// Return the number of bytes waiting to be consumed.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+    /// Return whether there are no bytes waiting to be consumed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+    /// Consume a single byte.
+    pub fn pop(&mut self) -> Option<u8> {
+        let mut byte = [0];
+        (self.read(&mut byte) == 1).then_some(byte[0])
+    }
+    /// Consume bytes into `buf` until it's full or the ring buffer is empty.
+    ///
+    /// Return the number of bytes consumed.
+    pub fn read(&mut self, buf: &mut [u8]) -> usize {
+        let head = self.ring.head.load(Ordering::Relaxed);
+        let tail = self.ring.tail.load(Ordering::Acquire);
+        let len = RingBuffer::<N>::distance(head, tail).min(buf.len());
+        for (i, byte) in buf[..len].iter_mut().enumerate() {
+            *byte = self.ring.slot(RingBuffer::<N>::advance(head, i)).load(Ordering::Relaxed);
+        }
+        self.ring.head.store(RingBuffer::<N>::advance(head, len), Ordering::Release);
+        len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer() {
+        let mut ring = RingBuffer::<8>::default();
+        assert_eq!(ring.capacity(), 8);
+        let (mut producer, mut consumer) = ring.split();
+        assert!(consumer.is_empty());
+        assert_eq!(consumer.pop(), None);
+        producer.write(b"Hello").unwrap();
+        assert_eq!(consumer.len(), 5);
+        assert_eq!(producer.free(), 3);
+        assert_eq!(producer.write(b"Hell"), Err(SerError::BufferFull));
+        let mut buf = [0u8;8];
+        assert_eq!(consumer.read(&mut buf[..4]), 4);
+        assert_eq!(&buf[..4], b"Hell");
+        producer.write(b" World").unwrap();
+        assert_eq!(producer.free(), 1);
+        assert_eq!(consumer.pop(), Some(b'o'));
+        assert_eq!(consumer.read(&mut buf), 6);
+        assert_eq!(&buf[..6], b" World");
+        assert!(consumer.is_empty());
+        assert_eq!(producer.free(), 8);
+    }
+
+    #[test]
+    fn test_ring_buffer_reservation() {
+        let mut ring = RingBuffer::<8>::new();
+        let (mut producer, mut consumer) = ring.split();
+        let mut buf = [0u8;8];
+        producer.write(b"123").unwrap();
+        let mut rsv = producer.reserve();
+        assert!(rsv.is_empty());
+        rsv.write(b"Hello").unwrap();
+        assert_eq!(rsv.write_byte(b'!'), Err(SerError::BufferFull));
+        assert_eq!(rsv.len(), 5);
+        assert_eq!(consumer.read(&mut buf), 3);
+        assert_eq!(&buf[..3], b"123");
+        rsv.write(b"!!!").unwrap();
+        rsv.commit();
+        assert_eq!(consumer.len(), 8);
+        let mut rsv = producer.reserve();
+        assert_eq!(rsv.write_byte(b'x'), Err(SerError::BufferFull));
+        rsv.abort();
+        assert_eq!(consumer.read(&mut buf[..6]), 6);
+        assert_eq!(&buf[..6], b"Hello!");
+        {
+            let mut rsv = producer.reserve();
+            rsv.write(b"Bye").unwrap();
+        }
+        assert_eq!(consumer.len(), 2);
+        let mut rsv = producer.reserve();
+        rsv.write_str("Bye").unwrap();
+        rsv.commit();
+        assert_eq!(consumer.read(&mut buf), 5);
+        assert_eq!(&buf[..5], b"!!Bye");
+        assert!(consumer.is_empty());
+    }
+
+    #[cfg(target_has_atomic = "8")]
+    #[test]
+    fn test_ring_buffer_try_split() {
+        static RING: RingBuffer<8> = RingBuffer::new();
+        let (mut producer, mut consumer): (Producer<'static, 8>, Consumer<'static, 8>) = RING.try_split().unwrap();
+        assert!(RING.try_split().is_none());
+        producer.write(b"Hello").unwrap();
+        let mut buf = [0u8;8];
+        assert_eq!(consumer.read(&mut buf), 5);
+        assert_eq!(&buf[..5], b"Hello");
+        assert!(RING.try_split().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_ring_buffer_threads() {
+        let mut ring = RingBuffer::<7>::new();
+        let (mut producer, mut consumer) = ring.split();
+        std::thread::scope(|s| {
+            s.spawn(move || {
+                for i in 0..1000u32 {
+                    loop {
+                        let mut rsv = producer.reserve();
+                        if rsv.write(&i.to_le_bytes()).is_ok() {
+                            rsv.commit();
+                            break
+                        }
+                        rsv.abort();
+                        std::thread::yield_now();
+                    }
+                }
+            });
+            let mut buf = [0u8;4];
+            for i in 0..1000u32 {
+                let mut len = 0;
+                while len < 4 {
+                    match consumer.read(&mut buf[len..]) {
+                        0 => std::thread::yield_now(),
+                        n => len += n
+                    }
+                }
+                assert_eq!(u32::from_le_bytes(buf), i);
+            }
+        });
+    }
+}
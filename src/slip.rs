@@ -0,0 +1,149 @@
+//! Serial Line Internet Protocol (SLIP) framing as specified in RFC 1055.
+//!
+//! Encoded frames are terminated with a single [`END`] byte.
+use super::*;
+
+/// The frame delimiter
+pub const END: u8 = 0xC0;
+/// The escape byte
+pub const ESC: u8 = 0xDB;
+/// Escaped [`END`] following [`ESC`]
+pub const ESC_END: u8 = 0xDC;
+/// Escaped [`ESC`] following [`ESC`]
+pub const ESC_ESC: u8 = 0xDD;
+
+/// A writer encoding data with SLIP on the fly into the inner writer.
+///
+/// Call [`SlipWriter::finish`] to write the terminating delimiter.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SlipWriter<W>(W);
+
+impl<W> SlipWriter<W> {
+    /// Create a new instance starting a new frame.
+    #[inline(always)]
+    pub fn new(inner: W) -> Self {
+        SlipWriter(inner)
+    }
+    /// Provide read-only access to the inner writer.
+    #[inline(always)]
+    pub fn writer_ref(&self) -> &W {
+        &self.0
+    }
+}
+
+impl<W: SerWrite> SlipWriter<W> {
+    /// Create a new instance writing an [`END`] byte first to flush any line noise
+    /// at the receiving end.
+    pub fn with_leading_end(mut inner: W) -> Result<Self, W::Error> {
+        inner.write_byte(END)?;
+        Ok(SlipWriter(inner))
+    }
+    /// Write the [`END`] delimiter and return the inner writer.
+    pub fn finish(mut self) -> Result<W, W::Error> {
+        self.0.write_byte(END)?;
+        Ok(self.0)
+    }
+}
+
+impl<W: SerWrite> SerWrite for SlipWriter<W> {
+    type Error = W::Error;
+
+    fn write(&mut self, mut buf: &[u8]) -> Result<(), Self::Error> {
+        while let Some(pos) = buf.iter().position(|&b| b == END || b == ESC) {
+            self.0.write(&buf[..pos])?;
+            self.write_byte(buf[pos])?;
+            buf = &buf[pos + 1..];
+        }
+        self.0.write(buf)
+    }
+
+    fn write_byte(&mut self, byte: u8) -> Result<(), Self::Error> {
+        match byte {
+            END => self.0.write(&[ESC, ESC_END]),
+            ESC => self.0.write(&[ESC, ESC_ESC]),
+            _ => self.0.write_byte(byte)
+        }
+    }
+}
+
+/// Decode a SLIP encoded frame in-place until the first [`END`] delimiter following
+/// the frame data or until the end of the slice.
+///
+/// Any leading [`END`] bytes are skipped.
+///
+/// Return the decoded portion of the slice.
+///
+/// Return [`SerError::InvalidFrame`] on an invalid escape sequence.
+pub fn decode_in_place(buf: &mut [u8]) -> SerResult<&mut [u8]> {
+    let mut rd = buf.iter().position(|&b| b != END).unwrap_or(buf.len());
+    let mut wr = 0;
+    while let Some(&byte) = buf.get(rd) {
+        rd += 1;
+        buf[wr] = match byte {
+            END => break,
+            ESC => {
+                let byte = match buf.get(rd) {
+                    Some(&ESC_END) => END,
+                    Some(&ESC_ESC) => ESC,
+                    _ => return Err(SerError::InvalidFrame)
+                };
+                rd += 1;
+                byte
+            }
+            byte => byte
+        };
+        wr += 1;
+    }
+    Ok(&mut buf[..wr])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slip() {
+        let mut out = [0u8;32];
+        for (input, expected) in [
+            (&b""[..], &b"\xC0"[..]),
+            (b"Hello", b"Hello\xC0"),
+            (b"\xC0", b"\xDB\xDC\xC0"),
+            (b"\xDB", b"\xDB\xDD\xC0"),
+            (b"a\xC0b\xDBc\xDC\xDD", b"a\xDB\xDCb\xDB\xDDc\xDC\xDD\xC0"),
+        ] {
+            let mut writer = SlipWriter::new(SliceWriter::new(&mut out));
+            writer.write(input).unwrap();
+            let encoded = writer.finish().unwrap().split().0;
+            assert_eq!(encoded, expected);
+            assert_eq!(decode_in_place(encoded).unwrap(), input);
+            let mut writer = SlipWriter::with_leading_end(SliceWriter::new(&mut out)).unwrap();
+            for &byte in input {
+                writer.write_byte(byte).unwrap();
+            }
+            let encoded = writer.finish().unwrap().split().0;
+            assert_eq!(encoded[0], END);
+            assert_eq!(&encoded[1..], expected);
+            assert_eq!(decode_in_place(encoded).unwrap(), input);
+        }
+    }
+
+    #[test]
+    fn test_slip_decode() {
+        let mut frame = *b"\xC0\xC0ab\xDB\xDC\xC0cd\xC0";
+        assert_eq!(decode_in_place(&mut frame).unwrap(), b"ab\xC0");
+        assert_eq!(decode_in_place(&mut frame[7..]).unwrap(), b"cd");
+        assert_eq!(decode_in_place(&mut []).unwrap(), b"");
+        assert_eq!(decode_in_place(&mut [END, END]).unwrap(), b"");
+        assert_eq!(decode_in_place(&mut [ESC]), Err(SerError::InvalidFrame));
+        assert_eq!(decode_in_place(&mut [ESC, END]), Err(SerError::InvalidFrame));
+    }
+
+    #[test]
+    fn test_slip_writer_error() {
+        let mut out = [0u8;4];
+        let mut writer = SlipWriter::new(SliceWriter::new(&mut out));
+        writer.write(b"ab\xC0").unwrap();
+        assert_eq!(writer.writer_ref().as_ref(), b"ab\xDB\xDC");
+        assert_eq!(writer.finish().unwrap_err(), SerError::BufferFull);
+    }
+}
@@ -0,0 +1,347 @@
+//! Page-buffered adapters for `embedded-storage` flash and EEPROM devices.
+use embedded_storage::Storage;
+use embedded_storage::nor_flash::NorFlash;
+
+use super::*;
+
+/// A buffer collecting data up to the next boundary of an `N`-byte page.
+#[derive(Debug, Clone)]
+struct PageBuffer<const N: usize> {
+    page: [u8; N],
+    len: usize,
+    /// The storage offset of the first byte in the `page`
+    offset: u32,
+    start: u32
+}
+
+impl<const N: usize> PageBuffer<N> {
+    fn new(offset: u32) -> Self {
+        PageBuffer { page: [0; N], len: 0, offset, start: offset }
+    }
+    #[inline]
+    fn written(&self) -> usize {
+        (self.offset - self.start) as usize + self.len
+    }
+    fn write<E, F>(&mut self, mut buf: &[u8], mut store: F) -> Result<(), E>
+        where F: FnMut(u32, &[u8]) -> Result<(), E>
+    {
+        while !buf.is_empty() {
+            let room = N - self.offset as usize % N - self.len;
+            let (head, tail) = buf.split_at(room.min(buf.len()));
+            self.page[self.len..self.len + head.len()].copy_from_slice(head);
+            if head.len() == room {
+                let size = self.len + room;
+                store(self.offset, &self.page[..size])?;
+                self.offset += size as u32;
+                self.len = 0;
+            }
+            else {
+                self.len += head.len();
+            }
+            buf = tail;
+        }
+        Ok(())
+    }
+    /// Store the first `size` bytes of the `page`, which may exceed the buffered length.
+    fn flush<E, F>(&mut self, size: usize, mut store: F) -> Result<(), E>
+        where F: FnMut(u32, &[u8]) -> Result<(), E>
+    {
+        if size != 0 {
+            store(self.offset, &self.page[..size])?;
+        }
+        self.offset += self.len as u32;
+        self.len = 0;
+        Ok(())
+    }
+}
+
+/// A writer buffering data in pages of `N` bytes and writing them to a [`NorFlash`] device.
+///
+/// Data is written to consecutive offsets starting from the one provided to [`NorFlashWriter::new`].
+/// Pages are aligned to multiples of `N` bytes, so the first page may be shorter.
+/// The target region must be erased beforehand, see [`NorFlash::erase`].
+///
+/// Call [`NorFlashWriter::finish`] to write the last partial page. It's padded with `0xFF`
+/// bytes to a multiple of [`NorFlash::WRITE_SIZE`].
+///
+/// The original error type of the device is passed through on failure.
+/// New bytes are buffered only after the page they complete has been written,
+/// so a failed write can be retried, unless it spanned more than one page,
+/// in which case its leading pages may have already been written.
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded-storage")))]
+#[derive(Debug, Clone)]
+pub struct NorFlashWriter<F, const N: usize> {
+    flash: F,
+    buffer: PageBuffer<N>
+}
+
+impl<F: NorFlash, const N: usize> NorFlashWriter<F, N> {
+    /// Create a new instance writing to `flash` from the `offset`.
+    ///
+    /// # Panics
+    /// Panics if the `offset` is not a multiple of [`NorFlash::WRITE_SIZE`].
+    pub fn new(flash: F, offset: u32) -> Self {
+        const { assert!(N > 0 && N.is_multiple_of(F::WRITE_SIZE), "the page size must be a multiple of the flash write size") };
+        assert!((offset as usize).is_multiple_of(F::WRITE_SIZE), "the offset must be aligned to the flash write size");
+        NorFlashWriter { flash, buffer: PageBuffer::new(offset) }
+    }
+    /// Return the total number of bytes written, including buffered ones.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.written()
+    }
+    /// Return whether nothing has been written.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Return the number of bytes buffered and not yet written to the device.
+    #[inline]
+    pub fn buffered(&self) -> usize {
+        self.buffer.len
+    }
+    /// Provide read-only access to the device.
+    #[inline]
+    pub fn flash_ref(&self) -> &F {
+        &self.flash
+    }
+    /// Write the last partial page, padded to a multiple of [`NorFlash::WRITE_SIZE`],
+    /// and return the device.
+    pub fn finish(mut self) -> Result<F, F::Error> {
+        let len = self.buffer.len;
+        let size = len.next_multiple_of(F::WRITE_SIZE);
+        self.buffer.page[len..size].fill(0xFF);
+        let flash = &mut self.flash;
+        self.buffer.flush(size, |offset, page| flash.write(offset, page))?;
+        Ok(self.flash)
+    }
+}
+
+impl<F: NorFlash, const N: usize> SerWrite for NorFlashWriter<F, N> {
+    type Error = F::Error;
+
+    fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        let flash = &mut self.flash;
+        self.buffer.write(buf, |offset, page| flash.write(offset, page))
+    }
+}
+
+/// A writer buffering data in pages of `N` bytes and writing them to a [`Storage`] device,
+/// e.g. an EEPROM.
+///
+/// Data is written to consecutive offsets starting from the one provided to [`StorageWriter::new`].
+/// Pages are aligned to multiples of `N` bytes, so the first page may be shorter.
+///
+/// Call [`StorageWriter::finish`] to write the last partial page.
+///
+/// The original error type of the device is passed through on failure.
+/// Retrying a failed write is safe under the same conditions as for [`NorFlashWriter`].
+#[cfg_attr(docsrs, doc(cfg(feature = "embedded-storage")))]
+#[derive(Debug, Clone)]
+pub struct StorageWriter<S, const N: usize> {
+    storage: S,
+    buffer: PageBuffer<N>
+}
+
+impl<S: Storage, const N: usize> StorageWriter<S, N> {
+    /// Create a new instance writing to `storage` from the `offset`.
+    pub fn new(storage: S, offset: u32) -> Self {
+        const { assert!(N > 0, "the page size must not be zero") };
+        StorageWriter { storage, buffer: PageBuffer::new(offset) }
+    }
+    /// Return the total number of bytes written, including buffered ones.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buffer.written()
+    }
+    /// Return whether nothing has been written.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Return the number of bytes buffered and not yet written to the device.
+    #[inline]
+    pub fn buffered(&self) -> usize {
+        self.buffer.len
+    }
+    /// Provide read-only access to the device.
+    #[inline]
+    pub fn storage_ref(&self) -> &S {
+        &self.storage
+    }
+    /// Write the last partial page and return the device.
+    pub fn finish(mut self) -> Result<S, S::Error> {
+        let storage = &mut self.storage;
+        self.buffer.flush(self.buffer.len, |offset, page| storage.write(offset, page))?;
+        Ok(self.storage)
+    }
+}
+
+impl<S: Storage, const N: usize> SerWrite for StorageWriter<S, N> {
+    type Error = S::Error;
+
+    fn write(&mut self, buf: &[u8]) -> Result<(), Self::Error> {
+        let storage = &mut self.storage;
+        self.buffer.write(buf, |offset, page| storage.write(offset, page))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+    use embedded_storage::ReadStorage;
+    use embedded_storage::nor_flash::{ErrorType, NorFlashErrorKind, ReadNorFlash};
+    use super::*;
+
+    /// An in-memory flash recording the sizes of the writes.
+    #[derive(Debug)]
+    struct MockFlash {
+        mem: [u8; 64],
+        writes: [usize; 16],
+        count: usize,
+        /// The number of upcoming writes to fail
+        failures: Cell<usize>
+    }
+
+    impl MockFlash {
+        fn new() -> Self {
+            MockFlash { mem: [0xFF; 64], writes: [0; 16], count: 0, failures: Cell::new(0) }
+        }
+        fn writes(&self) -> &[usize] {
+            &self.writes[..self.count]
+        }
+        fn store(&mut self, offset: u32, bytes: &[u8]) -> Result<(), NorFlashErrorKind> {
+            if self.failures.get() != 0 {
+                self.failures.set(self.failures.get() - 1);
+                return Err(NorFlashErrorKind::Other)
+            }
+            let start = offset as usize;
+            let target = self.mem.get_mut(start..start + bytes.len()).ok_or(NorFlashErrorKind::OutOfBounds)?;
+            target.copy_from_slice(bytes);
+            self.writes[self.count] = bytes.len();
+            self.count += 1;
+            Ok(())
+        }
+    }
+
+    impl ErrorType for MockFlash {
+        type Error = NorFlashErrorKind;
+    }
+
+    impl ReadNorFlash for MockFlash {
+        const READ_SIZE: usize = 1;
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+            let start = offset as usize;
+            bytes.copy_from_slice(&self.mem[start..start + bytes.len()]);
+            Ok(())
+        }
+        fn capacity(&self) -> usize {
+            self.mem.len()
+        }
+    }
+
+    impl NorFlash for MockFlash {
+        const WRITE_SIZE: usize = 4;
+        const ERASE_SIZE: usize = 32;
+
+        fn erase(&mut self, from: u32, to: u32) -> Result<(), Self::Error> {
+            self.mem[from as usize..to as usize].fill(0xFF);
+            Ok(())
+        }
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+            if !(offset as usize).is_multiple_of(Self::WRITE_SIZE) || !bytes.len().is_multiple_of(Self::WRITE_SIZE) {
+                return Err(NorFlashErrorKind::NotAligned)
+            }
+            let start = offset as usize;
+            if self.mem[start..].iter().zip(bytes).any(|(&old, &new)| old & new != new) {
+                return Err(NorFlashErrorKind::Other)
+            }
+            self.store(offset, bytes)
+        }
+    }
+
+    impl ReadStorage for MockFlash {
+        type Error = NorFlashErrorKind;
+
+        fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), Self::Error> {
+            ReadNorFlash::read(self, offset, bytes)
+        }
+        fn capacity(&self) -> usize {
+            self.mem.len()
+        }
+    }
+
+    impl Storage for MockFlash {
+        fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), Self::Error> {
+            self.store(offset, bytes)
+        }
+    }
+
+    #[test]
+    fn test_nor_flash_writer() {
+        let mut writer = NorFlashWriter::<_, 16>::new(MockFlash::new(), 4);
+        assert!(writer.is_empty());
+        writer.write(b"Hello").unwrap();
+        writer.write_byte(b' ').unwrap();
+        assert_eq!(writer.buffered(), 6);
+        assert!(writer.flash_ref().writes().is_empty());
+        writer.write_str("World! Hello again!").unwrap();
+        assert_eq!(writer.len(), 25);
+        assert_eq!(writer.buffered(), 13);
+        assert_eq!(writer.flash_ref().writes(), [12]);
+        let flash = writer.finish().unwrap();
+        assert_eq!(flash.writes(), [12, 16]);
+        assert_eq!(&flash.mem[..36], b"\xFF\xFF\xFF\xFFHello World! Hello again!\xFF\xFF\xFF\xFF\xFF\xFF\xFF");
+
+        let writer = NorFlashWriter::<_, 16>::new(flash, 32);
+        let flash = writer.finish().unwrap();
+        assert_eq!(flash.writes(), [12, 16]);
+
+        let mut writer = NorFlashWriter::<_, 32>::new(flash, 20);
+        writer.write(b"Bye!").unwrap();
+        assert_eq!(writer.finish().unwrap_err(), NorFlashErrorKind::Other);
+
+        let mut writer = NorFlashWriter::<_, 8>::new(MockFlash::new(), 56);
+        writer.write(b"12345678").unwrap();
+        assert_eq!(writer.write(b"9"), Ok(()));
+        assert_eq!(writer.finish().unwrap_err(), NorFlashErrorKind::OutOfBounds);
+
+        let mut writer = NorFlashWriter::<_, 16>::new(MockFlash::new(), 0);
+        writer.write(b"0123456789abcd").unwrap();
+        writer.flash_ref().failures.set(1);
+        assert_eq!(writer.write(b"efgh"), Err(NorFlashErrorKind::Other));
+        assert_eq!(writer.buffered(), 14);
+        assert_eq!(writer.len(), 14);
+        writer.write(b"efgh").unwrap();
+        assert_eq!(writer.buffered(), 2);
+        assert_eq!(writer.len(), 18);
+        let flash = writer.finish().unwrap();
+        assert_eq!(flash.writes(), [16, 4]);
+        assert_eq!(&flash.mem[..20], b"0123456789abcdefgh\xFF\xFF");
+    }
+
+    #[test]
+    #[should_panic(expected = "the offset must be aligned to the flash write size")]
+    fn test_nor_flash_writer_unaligned() {
+        let _ = NorFlashWriter::<_, 16>::new(MockFlash::new(), 2);
+    }
+
+    #[test]
+    fn test_storage_writer() {
+        let mut writer = StorageWriter::<_, 8>::new(MockFlash::new(), 5);
+        writer.write(b"Hello World!").unwrap();
+        assert_eq!(writer.storage_ref().writes(), [3, 8]);
+        assert_eq!(writer.buffered(), 1);
+        let mut storage = writer.finish().unwrap();
+        assert_eq!(storage.writes(), [3, 8, 1]);
+        let mut buf = [0u8; 12];
+        ReadStorage::read(&mut storage, 5, &mut buf).unwrap();
+        assert_eq!(&buf, b"Hello World!");
+
+        let mut writer = StorageWriter::<_, 8>::new(storage, 60);
+        writer.write(b"12345").unwrap();
+        assert_eq!(writer.storage_ref().writes(), [3, 8, 1, 4]);
+        assert_eq!(writer.finish().unwrap_err(), NorFlashErrorKind::OutOfBounds);
+    }
+}
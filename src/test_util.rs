@@ -0,0 +1,57 @@
+//! Helpers for testing serializers and writer wrappers.
+//!
+//! Not a part of the public API, enabled by the `test-util` feature.
+use super::*;
+
+/// A writer recording calls to [`SerWrite::write`], [`SerWrite::write_vectored`]
+/// and [`SerWrite::reserve`] to verify how serializers and wrappers use the inner writer.
+pub struct CallRecorder<'a> {
+    /// The writer receiving the data
+    pub writer: SliceWriter<'a>,
+    /// The number of [`SerWrite::write`] calls, including the default
+    /// [`SerWrite::write_byte`] and [`SerWrite::write_str`] implementations
+    pub writes: usize,
+    /// The number of [`SerWrite::write_vectored`] calls
+    pub vectored: usize,
+    /// The sum of all [`SerWrite::reserve`] requests
+    pub reserved: usize
+}
+
+impl<'a> CallRecorder<'a> {
+    /// Create a new instance writing to `buf`.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        CallRecorder { writer: SliceWriter::new(buf), writes: 0, vectored: 0, reserved: 0 }
+    }
+}
+
+impl SerWrite for CallRecorder<'_> {
+    type Error = SerError;
+
+    fn write(&mut self, buf: &[u8]) -> SerResult<()> {
+        self.writes += 1;
+        self.writer.write(buf)
+    }
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> SerResult<()> {
+        self.vectored += 1;
+        self.writer.write_vectored(bufs)
+    }
+    fn reserve(&mut self, additional: usize) -> SerResult<()> {
+        self.reserved += additional;
+        Ok(())
+    }
+}
+
+impl SerCheckpoint for CallRecorder<'_> {
+    fn checkpoint(&self) -> usize {
+        self.writer.checkpoint()
+    }
+    fn rollback(&mut self, checkpoint: usize) {
+        self.writer.rollback(checkpoint)
+    }
+}
+
+impl AsMut<[u8]> for CallRecorder<'_> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.writer.as_mut()
+    }
+}
@@ -0,0 +1,165 @@
+//! A writer capturing a window of the output.
+use super::*;
+
+/// A writer capturing only the bytes of the output in the range `[offset, offset + buf.len())`.
+///
+/// Bytes before the window are discarded and bytes after the window are counted only,
+/// so the same message can be serialized repeatedly to transmit it in pages without
+/// holding all of it in memory.
+///
+/// Writing never fails.
+#[derive(Debug, PartialEq, Eq)]
+pub struct WindowWriter<'a> {
+    buf: &'a mut [u8],
+    offset: usize,
+    pos: usize
+}
+
+/// A captured window of the output returned from [`WindowWriter::finish`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Window<'a> {
+    /// The bytes captured in the window
+    pub data: &'a mut [u8],
+    /// The total length of the output
+    pub total_len: usize,
+    /// Whether the window has been filled completely
+    ///
+    /// If `false`, `data` is the last page of the output.
+    pub full: bool
+}
+
+impl<'a> WindowWriter<'a> {
+    /// Create a new instance capturing the output to `buf` beginning at the `offset`.
+    ///
+    /// # Panics
+    /// Panics if `buf` is empty.
+    #[inline]
+    pub fn new(buf: &'a mut [u8], offset: usize) -> Self {
+        assert!(!buf.is_empty(), "the window buffer must not be empty");
+        WindowWriter { buf, offset, pos: 0 }
+    }
+    /// Return the offset of the window.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+    /// Return the total number of bytes written so far.
+    #[inline]
+    pub fn total_len(&self) -> usize {
+        self.pos
+    }
+    /// Return the number of bytes captured so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pos.saturating_sub(self.offset).min(self.buf.len())
+    }
+    /// Return whether no bytes have been captured so far.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Return whether the window has been filled completely.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.buf.len()
+    }
+    /// Destruct self returning the captured window.
+    pub fn finish(self) -> Window<'a> {
+        let len = self.len();
+        let full = len == self.buf.len();
+        Window { data: &mut self.buf[..len], total_len: self.pos, full }
+    }
+}
+
+impl AsRef<[u8]> for WindowWriter<'_> {
+    /// Return the bytes captured so far.
+    fn as_ref(&self) -> &[u8] {
+        &self.buf[..self.len()]
+    }
+}
+
+impl SerWrite for WindowWriter<'_> {
+    type Error = SerError;
+
+    fn write(&mut self, buf: &[u8]) -> SerResult<()> {
+        let start = self.pos;
+        self.pos = self.pos.saturating_add(buf.len());
+        let begin = self.offset.max(start);
+        let end = self.pos.min(self.offset.saturating_add(self.buf.len()));
+        if begin < end {
+            self.buf[begin - self.offset..end - self.offset]
+                .copy_from_slice(&buf[begin - start..end - start]);
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn write_byte(&mut self, byte: u8) -> SerResult<()> {
+        if let Some(target) = self.pos.checked_sub(self.offset)
+                                      .and_then(|index| self.buf.get_mut(index))
+        {
+            *target = byte;
+        }
+        self.pos = self.pos.saturating_add(1);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_writer() {
+        let message = b"Hello World! Good Bye!";
+        let mut buf = [0u8;5];
+        let mut offset = 0;
+        let mut pages = 0;
+        let mut out = [0u8;32];
+        let mut out = SliceWriter::new(&mut out);
+        loop {
+            let mut writer = WindowWriter::new(&mut buf, offset);
+            assert_eq!(writer.offset(), offset);
+            assert!(writer.is_empty());
+            writer.write(&message[..7]).unwrap();
+            writer.write_byte(message[7]).unwrap();
+            writer.write_str("rld! Good Bye!").unwrap();
+            assert_eq!(writer.total_len(), message.len());
+            assert_eq!(writer.as_ref().len(), writer.len());
+            let window = writer.finish();
+            assert_eq!(window.total_len, message.len());
+            out.write(window.data).unwrap();
+            offset += window.data.len();
+            pages += 1;
+            if !window.full {
+                break
+            }
+        }
+        assert_eq!(pages, 5);
+        assert_eq!(out.as_ref(), message);
+
+        let mut writer = WindowWriter::new(&mut buf, 20);
+        writer.write(message).unwrap();
+        assert!(!writer.is_full());
+        assert_eq!(writer.finish(), Window { data: &mut b"e!".clone(), total_len: 22, full: false });
+        let mut writer = WindowWriter::new(&mut buf, 30);
+        writer.write(message).unwrap();
+        assert!(writer.is_empty());
+        assert_eq!(writer.finish(), Window { data: &mut [], total_len: 22, full: false });
+        let mut writer = WindowWriter::new(&mut buf, 17);
+        writer.write(message).unwrap();
+        assert!(writer.is_full());
+        assert_eq!(writer.finish(), Window { data: &mut b" Bye!".clone(), total_len: 22, full: true });
+        let mut writer = WindowWriter::new(&mut buf, usize::MAX - 2);
+        writer.write(message).unwrap();
+        writer.write_byte(b'!').unwrap();
+        assert!(writer.is_empty());
+        assert_eq!(writer.finish(), Window { data: &mut [], total_len: 23, full: false });
+    }
+
+    #[test]
+    #[should_panic(expected = "the window buffer must not be empty")]
+    fn test_window_writer_empty() {
+        let _ = WindowWriter::new(&mut [], 0);
+    }
+}